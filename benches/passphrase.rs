@@ -0,0 +1,70 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use niceware::LookupStrategy;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+/// Phrase lengths in words: a short code, a 128-bit key, a 256-bit key and the maximum.
+const LENGTHS: [usize; 4] = [4, 8, 16, 512];
+
+fn random_bytes(num_words: usize) -> Vec<u8> {
+    let mut bytes = vec![0; num_words * 2];
+    StdRng::seed_from_u64(num_words as u64).fill_bytes(&mut bytes);
+    bytes
+}
+
+fn encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+    for num_words in LENGTHS {
+        let bytes = random_bytes(num_words);
+        group.throughput(Throughput::Elements(num_words as u64));
+        group.bench_with_input(BenchmarkId::new("vec", num_words), &bytes, |b, bytes| {
+            b.iter(|| niceware::bytes_to_passphrase(black_box(bytes)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("join", num_words), &bytes, |b, bytes| {
+            b.iter(|| niceware::bytes_to_passphrase_string(black_box(bytes), " ").unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    let previous = niceware::lookup_strategy();
+    #[allow(unused_mut)]
+    let mut strategies = vec![
+        ("binary_search", LookupStrategy::BinarySearch),
+        ("hash_map", LookupStrategy::HashMap),
+    ];
+    #[cfg(feature = "phf")]
+    strategies.push(("perfect_hash", LookupStrategy::PerfectHash));
+    for (name, strategy) in strategies {
+        niceware::set_lookup_strategy(strategy);
+        for num_words in LENGTHS {
+            let words = niceware::bytes_to_passphrase(&random_bytes(num_words)).unwrap();
+            group.throughput(Throughput::Elements(num_words as u64));
+            group.bench_with_input(BenchmarkId::new(name, num_words), &words, |b, words| {
+                b.iter(|| niceware::passphrase_to_bytes(black_box(words)).unwrap())
+            });
+        }
+    }
+    niceware::set_lookup_strategy(previous);
+    group.finish();
+}
+
+fn generate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate");
+    let mut rng = StdRng::seed_from_u64(0);
+    for num_words in LENGTHS {
+        group.throughput(Throughput::Elements(num_words as u64));
+        #[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+        group.bench_function(BenchmarkId::new("os_rng", num_words), |b| {
+            b.iter(|| niceware::generate_passphrase(black_box(num_words)).unwrap())
+        });
+        group.bench_function(BenchmarkId::new("std_rng", num_words), |b| {
+            b.iter(|| niceware::generate_passphrase_with(black_box(num_words), &mut rng).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, encode, decode, generate);
+criterion_main!(benches);
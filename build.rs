@@ -0,0 +1,84 @@
+//! Generate the perfect hash table of the `phf` feature from the wordlist.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+#[path = "src/phf.rs"]
+mod phf;
+
+#[allow(dead_code)]
+mod words {
+    include!("src/words.rs");
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/phf.rs");
+    println!("cargo:rerun-if-changed=src/words.rs");
+    if env::var_os("CARGO_FEATURE_PHF").is_none() {
+        return;
+    }
+
+    let (seed, displacements, slots) = (0..)
+        .find_map(table)
+        .expect("some seed gives a perfect hash");
+    let mut code = String::new();
+    writeln!(code, "const PHF_SEED: u64 = {};", seed).unwrap();
+    writeln!(
+        code,
+        "static PHF_DISPLACEMENTS: [u16; phf::BUCKETS] = {:?};",
+        displacements
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "static PHF_SLOTS: [u16; {}] = {:?};",
+        slots.len(),
+        slots
+    )
+    .unwrap();
+    let out = Path::new(&env::var_os("OUT_DIR").unwrap()).join("phf_table.rs");
+    fs::write(out, code).unwrap();
+}
+
+/// Build a minimal perfect hash with hash-and-displace, or `None` if `seed` gives two words of a
+/// bucket the same hash values.
+///
+/// Buckets are placed largest first, each with the smallest displacement that puts all its words
+/// in free slots. Slot `s` holds the index of the word placed there.
+fn table(seed: u64) -> Option<(u64, Vec<u16>, Vec<u16>)> {
+    let mut buckets = vec![Vec::new(); phf::BUCKETS];
+    for (index, word) in words::ALL_WORDS.iter().enumerate() {
+        let (bucket, f1, f2) = phf::hash(seed, word.as_bytes());
+        buckets[bucket].push((index as u16, f1, f2));
+    }
+    let mut order: Vec<usize> = (0..phf::BUCKETS).collect();
+    order.sort_by_key(|&bucket| std::cmp::Reverse(buckets[bucket].len()));
+
+    let mut displacements = vec![0; phf::BUCKETS];
+    let mut slots = vec![None; words::ALL_WORDS.len()];
+    let mut placed = Vec::new();
+    for bucket in order {
+        let words = &buckets[bucket];
+        let displacement = (0..=u16::MAX).find(|&displacement| {
+            placed.clear();
+            words.iter().all(|&(_, f1, f2)| {
+                let slot = phf::slot(f1, f2, displacement);
+                let free = slots[slot].is_none() && !placed.contains(&slot);
+                placed.push(slot);
+                free
+            })
+        })?;
+        displacements[bucket] = displacement;
+        for &(index, f1, f2) in words {
+            slots[phf::slot(f1, f2, displacement)] = Some(index);
+        }
+    }
+    Some((
+        seed,
+        displacements,
+        slots.into_iter().map(Option::unwrap).collect(),
+    ))
+}
@@ -0,0 +1,87 @@
+use crate::{pair_to_word, passphrase_to_bytes_into, Error};
+
+/// Create word-based passphrase of a fixed number of words from given bytes, without allocating.
+///
+/// ## Errors
+///
+/// This function returns a LengthMismatch error if `bytes` is not exactly two bytes per word.
+///
+/// ## Examples
+///
+/// ```
+/// let words: [&str; 2] = niceware::bytes_to_passphrase_array(&[0, 0, 255, 255]).unwrap();
+/// assert_eq!(words, ["a", "zyzzyva"]);
+/// ```
+pub fn bytes_to_passphrase_array<const W: usize>(bytes: &[u8]) -> Result<[&'static str; W], Error> {
+    if bytes.len() != W * 2 {
+        return Err(Error::LengthMismatch {
+            expected: W * 2,
+            actual: bytes.len(),
+        });
+    }
+
+    let mut words = [""; W];
+    for (word, pair) in words.iter_mut().zip(bytes.chunks_exact(2)) {
+        *word = pair_to_word(pair);
+    }
+    Ok(words)
+}
+
+/// Decode words into a fixed-size array of bytes, such as a 16-byte key, without allocating.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if `N` is odd, a LengthMismatch error if there are
+/// not exactly `N / 2` words, and an UnknownWord error if a word is not found in the dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// let key: [u8; 4] = niceware::passphrase_to_key(&["a", "zyzzyva"]).unwrap();
+/// assert_eq!(key, [0, 0, 255, 255]);
+/// ```
+pub fn passphrase_to_key<S: AsRef<str>, const N: usize>(words: &[S]) -> Result<[u8; N], Error> {
+    if N % 2 != 0 {
+        return Err(Error::InvalidSize { size: N });
+    }
+
+    let mut key = [0; N];
+    passphrase_to_bytes_into(words, &mut key)?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytes_to_passphrase, bytes_to_passphrase_array, passphrase_to_key, Error};
+
+    #[test]
+    fn fixed_sizes() {
+        let bytes = [0, 0, 17, 212, 12, 140, 255, 255];
+        let words: [&str; 4] = bytes_to_passphrase_array(&bytes).unwrap();
+        assert_eq!(words[..], bytes_to_passphrase(&bytes).unwrap()[..]);
+        assert_eq!(passphrase_to_key::<_, 8>(&words).unwrap(), bytes);
+
+        assert!(matches!(
+            bytes_to_passphrase_array::<3>(&bytes),
+            Err(Error::LengthMismatch {
+                expected: 6,
+                actual: 8
+            })
+        ));
+        assert!(matches!(
+            passphrase_to_key::<_, 16>(&words),
+            Err(Error::LengthMismatch {
+                expected: 8,
+                actual: 16
+            })
+        ));
+        assert!(matches!(
+            passphrase_to_key::<_, 7>(&words),
+            Err(Error::InvalidSize { size: 7 })
+        ));
+        assert!(matches!(
+            passphrase_to_key::<_, 4>(&["a", "ninetales"]),
+            Err(Error::UnknownWord { .. })
+        ));
+    }
+}
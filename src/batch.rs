@@ -0,0 +1,114 @@
+use crate::lookup::{ascii_lowercase, Lookup, LOWERCASE_BUF_LEN};
+use crate::wordlist::custom_default_wordlist;
+use crate::{unknown_word, Error, WordList};
+
+/// Decode many whitespace-separated passphrases, one result per phrase.
+///
+/// This is meant for bulk workloads such as log ingestion. The lookup strategy is resolved (and
+/// the hash map index built, if selected) once up front, and the buffer used to lowercase words is
+/// reused across all phrases. A phrase with an unknown word yields an error without affecting the
+/// phrases after it.
+///
+/// ## Examples
+///
+/// ```
+/// let phrases = ["a zyzzyva", "ninetales", "Zyzzyva A"];
+/// let decoded: Vec<_> = niceware::passphrases_to_bytes(&phrases).collect();
+/// assert_eq!(decoded[0].as_ref().unwrap(), &[0, 0, 255, 255]);
+/// assert!(decoded[1].is_err());
+/// assert_eq!(decoded[2].as_ref().unwrap(), &[255, 255, 0, 0]);
+/// ```
+pub fn passphrases_to_bytes<I>(phrases: I) -> PassphrasesToBytes<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    PassphrasesToBytes {
+        phrases: phrases.into_iter(),
+        lookup: Lookup::current(),
+        custom: custom_default_wordlist(),
+        lowercase: [0; LOWERCASE_BUF_LEN],
+    }
+}
+
+/// Iterator returned by `passphrases_to_bytes`.
+#[derive(Debug)]
+pub struct PassphrasesToBytes<I> {
+    phrases: I,
+    lookup: Lookup,
+    custom: Option<&'static dyn WordList>,
+    lowercase: [u8; LOWERCASE_BUF_LEN],
+}
+
+impl<I> PassphrasesToBytes<I> {
+    fn decode(&mut self, phrase: &str) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::with_capacity(phrase.len() / 4);
+        for word in phrase.split_whitespace() {
+            let lookup = self.lookup;
+            let index = match self.custom {
+                Some(list) => list.index_of(word),
+                None => {
+                    ascii_lowercase(word, &mut self.lowercase).and_then(|word| lookup.find(word))
+                }
+            }
+            .ok_or_else(|| unknown_word(word))?;
+            bytes.extend(&index.to_be_bytes());
+        }
+        Ok(bytes)
+    }
+}
+
+impl<I> Iterator for PassphrasesToBytes<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Result<Vec<u8>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let phrase = self.phrases.next()?;
+        Some(self.decode(phrase.as_ref()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.phrases.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{passphrase_to_bytes, passphrases_to_bytes};
+
+    #[test]
+    fn matches_single_decode() {
+        let phrases = vec![
+            "a bioengineering balloted gobbled".to_string(),
+            String::new(),
+            "  creneled\twritten\ndepriving ZYZZYVA ".to_string(),
+        ];
+        let decoded: Vec<Vec<u8>> = passphrases_to_bytes(&phrases)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        for (phrase, bytes) in phrases.iter().zip(decoded) {
+            let words: Vec<&str> = phrase.split_whitespace().collect();
+            assert_eq!(bytes, passphrase_to_bytes(&words).unwrap());
+        }
+    }
+
+    #[test]
+    fn errors_per_phrase() {
+        let long = "a".repeat(29);
+        let phrases = ["a ninetales", long.as_str(), "zyzzyva"];
+        let mut decoded = passphrases_to_bytes(phrases.iter());
+        assert_eq!(
+            decoded.next().unwrap().unwrap_err().to_string(),
+            "unknown word: ninetales"
+        );
+        assert_eq!(
+            decoded.next().unwrap().unwrap_err().to_string(),
+            format!("unknown word: {}", long)
+        );
+        assert_eq!(decoded.next().unwrap().unwrap(), &[255, 255]);
+        assert!(decoded.next().is_none());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{pair_to_word, Error};
+
+/// Incrementally build a passphrase from bytes as they arrive.
+///
+/// Bytes can be pushed one at a time or in slices of any length. Every complete pair of bytes
+/// becomes a word straight away; a dangling odd byte is held back until its partner arrives.
+///
+/// ## Examples
+///
+/// ```
+/// let mut builder = niceware::PhraseBuilder::new();
+/// builder.push_bytes(&[0, 0, 17]);
+/// assert_eq!(builder.take_words(), &["a"]);
+/// builder.push_byte(212);
+/// assert_eq!(builder.finish().unwrap(), &["bioengineering"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PhraseBuilder {
+    words: Vec<&'static str>,
+    pending: Option<u8>,
+    len: usize,
+}
+
+impl PhraseBuilder {
+    /// Create an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push a single byte.
+    pub fn push_byte(&mut self, byte: u8) {
+        self.len += 1;
+        match self.pending.take() {
+            Some(first) => self.words.push(pair_to_word(&[first, byte])),
+            None => self.pending = Some(byte),
+        }
+    }
+
+    /// Push a slice of bytes.
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        let mut bytes = bytes;
+        if let (Some(_), Some((&byte, rest))) = (self.pending, bytes.split_first()) {
+            self.push_byte(byte);
+            bytes = rest;
+        }
+
+        let pairs = bytes.chunks_exact(2);
+        if let [byte] = *pairs.remainder() {
+            self.pending = Some(byte);
+        }
+        self.words.extend(pairs.map(pair_to_word));
+        self.len += bytes.len();
+    }
+
+    /// Completed words that have not been taken yet.
+    pub fn words(&self) -> &[&'static str] {
+        &self.words
+    }
+
+    /// Remove and return the completed words, leaving any dangling byte in place.
+    pub fn take_words(&mut self) -> Vec<&'static str> {
+        std::mem::take(&mut self.words)
+    }
+
+    /// Whether an odd byte is waiting for its partner.
+    pub fn has_pending_byte(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Total number of bytes pushed so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no bytes have been pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the words that have not been taken yet.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an InvalidSize error if an odd number of bytes was pushed in total.
+    pub fn finish(self) -> Result<Vec<&'static str>, Error> {
+        match self.pending {
+            Some(_) => Err(Error::InvalidSize { size: self.len }),
+            None => Ok(self.words),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytes_to_passphrase, PhraseBuilder};
+
+    #[test]
+    fn matches_bytes_to_passphrase() {
+        let bytes = [
+            0, 0, 17, 212, 12, 140, 90, 246, 46, 83, 254, 60, 54, 169, 255, 255,
+        ];
+
+        for split in 0..bytes.len() {
+            let mut builder = PhraseBuilder::new();
+            builder.push_bytes(&bytes[..split]);
+            builder.push_bytes(&bytes[split..]);
+            assert_eq!(builder.len(), bytes.len());
+            assert_eq!(
+                builder.finish().unwrap(),
+                bytes_to_passphrase(&bytes).unwrap()
+            );
+        }
+
+        let mut builder = PhraseBuilder::new();
+        bytes.iter().for_each(|&byte| builder.push_byte(byte));
+        assert_eq!(
+            builder.finish().unwrap(),
+            bytes_to_passphrase(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn take_words_keeps_pending_byte() {
+        let mut builder = PhraseBuilder::new();
+        builder.push_bytes(&[255, 255, 0]);
+        assert!(builder.has_pending_byte());
+        assert_eq!(builder.take_words(), &["zyzzyva"]);
+        assert!(builder.words().is_empty());
+        builder.push_bytes(&[0]);
+        assert!(!builder.has_pending_byte());
+        assert_eq!(builder.take_words(), &["a"]);
+    }
+
+    #[test]
+    fn odd_length_finish() {
+        let mut builder = PhraseBuilder::new();
+        builder.push_bytes(&[0, 0, 1]);
+        assert_eq!(
+            builder.finish().unwrap_err().to_string(),
+            "odd size not supported: 3"
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{bytes_to_passphrase, word_to_index, Error};
+
+/// Create word-based passphrase from given bytes, carrying one extra bit per word in its case.
+///
+/// Each word is written in lowercase for a `false` bit and with its first letter capitalized for
+/// a `true` bit. Since decoding ignores case, the result is still a valid ordinary passphrase; the
+/// extra bits are only recovered by `capitalized_passphrase_to_bytes`.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes. It
+/// returns a LengthMismatch error if `bits` does not hold exactly one bit per word.
+///
+/// ## Examples
+///
+/// ```
+/// let words = niceware::bytes_to_capitalized_passphrase(&[0, 0, 255, 255], &[true, false]).unwrap();
+/// assert_eq!(words, &["A", "zyzzyva"]);
+/// ```
+pub fn bytes_to_capitalized_passphrase(bytes: &[u8], bits: &[bool]) -> Result<Vec<String>, Error> {
+    let words = bytes_to_passphrase(bytes)?;
+    if bits.len() != words.len() {
+        return Err(Error::LengthMismatch {
+            expected: words.len(),
+            actual: bits.len(),
+        });
+    }
+
+    Ok(words
+        .into_iter()
+        .zip(bits)
+        .map(|(word, &bit)| {
+            if bit {
+                capitalize(word)
+            } else {
+                word.to_string()
+            }
+        })
+        .collect())
+}
+
+/// Uppercase the first character of `word`, which may be more than one byte long.
+pub(crate) fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .into_iter()
+        .flat_map(char::to_uppercase)
+        .chain(chars)
+        .collect()
+}
+
+/// Decode words into bytes and the bits carried in their capitalization.
+///
+/// A word whose first letter is uppercase carries a `true` bit; the case of the remaining letters
+/// is ignored.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary.
+pub fn capitalized_passphrase_to_bytes(words: &[&str]) -> Result<(Vec<u8>, Vec<bool>), Error> {
+    let mut bytes = Vec::with_capacity(words.len() * 2);
+    let mut bits = Vec::with_capacity(words.len());
+
+    for word in words {
+        bytes.extend(&word_to_index(word)?.to_be_bytes());
+        bits.push(word.starts_with(char::is_uppercase));
+    }
+    Ok((bytes, bits))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytes_to_capitalized_passphrase, capitalized_passphrase_to_bytes};
+
+    #[test]
+    fn round_trip() {
+        let bytes = [0, 0, 17, 212, 12, 140, 90, 246];
+        let bits = [false, true, true, false];
+        let words = bytes_to_capitalized_passphrase(&bytes, &bits).unwrap();
+        assert_eq!(words, &["a", "Bioengineering", "Balloted", "gobbled"]);
+
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        assert_eq!(
+            capitalized_passphrase_to_bytes(&words).unwrap(),
+            (bytes.to_vec(), bits.to_vec())
+        );
+        assert_eq!(crate::passphrase_to_bytes(&words).unwrap(), bytes);
+    }
+
+    #[test]
+    fn only_first_letter_counts() {
+        assert_eq!(
+            capitalized_passphrase_to_bytes(&["aAH", "ZYZZYVA"]).unwrap(),
+            (vec![0, 1, 255, 255], vec![false, true])
+        );
+    }
+
+    #[test]
+    fn bit_count_must_match() {
+        assert_eq!(
+            bytes_to_capitalized_passphrase(&[0, 0], &[true, true])
+                .unwrap_err()
+                .to_string(),
+            "expected length 1 but got 2"
+        );
+    }
+}
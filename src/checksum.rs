@@ -0,0 +1,133 @@
+use crate::{bytes_to_passphrase, pair_to_word, word_to_index, Error};
+
+/// Number of payload words covered by each check word.
+pub const CHECK_GROUP_WORDS: usize = 4;
+
+/// Create word-based passphrase with a check word after every group of payload words.
+///
+/// Bytes are encoded as with `bytes_to_passphrase`, and after every `CHECK_GROUP_WORDS` words
+/// (and after a shorter final group) an extra word is inserted. The check word is a CRC-16 of the
+/// group's position and bytes, so a mistyped word or swapped groups are caught and localized by
+/// `checked_passphrase_to_bytes`.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes.
+pub fn bytes_to_checked_passphrase(bytes: &[u8]) -> Result<Vec<&'static str>, Error> {
+    let payload = bytes_to_passphrase(bytes)?;
+    let mut words = Vec::with_capacity(payload.len() + payload.len().div_ceil(CHECK_GROUP_WORDS));
+
+    for (group, chunk) in bytes.chunks(CHECK_GROUP_WORDS * 2).enumerate() {
+        words.extend(chunk.chunks_exact(2).map(pair_to_word));
+        words.push(pair_to_word(&group_checksum(group, chunk).to_be_bytes()));
+    }
+    Ok(words)
+}
+
+/// Decode words created by `bytes_to_checked_passphrase` and verify every check word.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary. It returns
+/// a ChecksumMismatch error listing the zero-based index of every group whose check word does not
+/// match, including a trailing check word with no payload.
+pub fn checked_passphrase_to_bytes(words: &[&str]) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(words.len() * 2);
+    let mut failed = Vec::new();
+
+    for (group, chunk) in words.chunks(CHECK_GROUP_WORDS + 1).enumerate() {
+        let (check_word, payload) = chunk.split_last().unwrap();
+        let start = bytes.len();
+        for word in payload {
+            bytes.extend(&word_to_index(word)?.to_be_bytes());
+        }
+        let check = word_to_index(check_word)?;
+        if payload.is_empty() || check != group_checksum(group, &bytes[start..]) {
+            failed.push(group);
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(bytes)
+    } else {
+        Err(Error::ChecksumMismatch { groups: failed })
+    }
+}
+
+/// CRC-16/CCITT-FALSE over the group index followed by the group's bytes.
+fn group_checksum(group: usize, bytes: &[u8]) -> u16 {
+    let group = group as u32;
+    group
+        .to_be_bytes()
+        .iter()
+        .chain(bytes)
+        .fold(0xffff, |crc, &byte| {
+            (0..8).fold(crc ^ (u16::from(byte) << 8), |crc, _| {
+                if crc & 0x8000 != 0 {
+                    (crc << 1) ^ 0x1021
+                } else {
+                    crc << 1
+                }
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytes_to_checked_passphrase, checked_passphrase_to_bytes, Error};
+
+    const BYTES: [u8; 20] = [
+        0, 0, 17, 212, 12, 140, 90, 246, 46, 83, 254, 60, 54, 169, 255, 255, 1, 2, 3, 4,
+    ];
+
+    #[test]
+    fn group_layout() {
+        assert!(bytes_to_checked_passphrase(&[]).unwrap().is_empty());
+        assert_eq!(bytes_to_checked_passphrase(&[0, 0]).unwrap().len(), 2);
+
+        let words = bytes_to_checked_passphrase(&BYTES).unwrap();
+        assert_eq!(words.len(), 13);
+        assert_eq!(&words[..4], &["a", "bioengineering", "balloted", "gobbled"]);
+        assert_eq!(
+            &words[5..9],
+            &["creneled", "written", "depriving", "zyzzyva"]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for len in (0..=BYTES.len()).step_by(2) {
+            let words = bytes_to_checked_passphrase(&BYTES[..len]).unwrap();
+            assert_eq!(checked_passphrase_to_bytes(&words).unwrap(), &BYTES[..len]);
+        }
+    }
+
+    #[test]
+    fn localizes_errors() {
+        let mut words = bytes_to_checked_passphrase(&BYTES).unwrap();
+        words[6] = "a";
+        words[11] = "zyzzyva";
+        match checked_passphrase_to_bytes(&words).unwrap_err() {
+            Error::ChecksumMismatch { groups } => assert_eq!(groups, &[1, 2]),
+            err => panic!("unexpected error: {}", err),
+        }
+
+        // Swapping two whole groups is caught too.
+        let words = bytes_to_checked_passphrase(&BYTES[..16]).unwrap();
+        let swapped = [&words[5..], &words[..5]].concat();
+        assert_eq!(
+            checked_passphrase_to_bytes(&swapped)
+                .unwrap_err()
+                .to_string(),
+            "checksum mismatch in groups: [0, 1]"
+        );
+
+        // A trailing check word without payload.
+        let mut words = bytes_to_checked_passphrase(&BYTES[..8]).unwrap();
+        words.push("a");
+        assert_eq!(
+            checked_passphrase_to_bytes(&words).unwrap_err().to_string(),
+            "checksum mismatch in groups: [1]"
+        );
+    }
+}
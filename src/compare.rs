@@ -0,0 +1,242 @@
+use crate::{passphrase_to_bytes, split_words, word_to_index, Error};
+
+/// How two passphrases of the same length differ.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Distance {
+    /// Zero-based positions of the words that differ.
+    pub word_positions: Vec<usize>,
+    /// Number of bytes that differ between the decoded passphrases.
+    pub differing_bytes: usize,
+}
+
+impl Distance {
+    /// Word-level Hamming distance, the number of words that differ.
+    pub fn words(&self) -> usize {
+        self.word_positions.len()
+    }
+
+    /// Whether the passphrases decode to the same bytes.
+    pub fn is_zero(&self) -> bool {
+        self.word_positions.is_empty()
+    }
+}
+
+/// Compare two passphrases of the same length word by word.
+///
+/// Words are compared by their position in the dictionary, so differences in case do not count.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word of either passphrase is not found in the
+/// dictionary, and a LengthMismatch error if the passphrases have different numbers of words.
+///
+/// ## Examples
+///
+/// ```
+/// let original = ["a", "bioengineering", "balloted", "gobbled"];
+/// let retyped = ["A", "bioengineering", "ballot", "gobbled"];
+/// let distance = niceware::distance(&original, &retyped).unwrap();
+/// assert_eq!(distance.word_positions, &[2]);
+/// ```
+pub fn distance(a: &[&str], b: &[&str]) -> Result<Distance, Error> {
+    if a.len() != b.len() {
+        return Err(Error::LengthMismatch {
+            expected: a.len(),
+            actual: b.len(),
+        });
+    }
+    let a = passphrase_to_bytes(a)?;
+    let b = passphrase_to_bytes(b)?;
+
+    let word_positions = a
+        .chunks_exact(2)
+        .zip(b.chunks_exact(2))
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+        .map(|(position, _)| position)
+        .collect();
+    let differing_bytes = a.iter().zip(&b).filter(|(a, b)| a != b).count();
+
+    Ok(Distance {
+        word_positions,
+        differing_bytes,
+    })
+}
+
+/// Check whether two passphrase strings decode to the same bytes.
+///
+/// Both strings may use any mix of case and any whitespace, commas or hyphens between words, so
+/// `"A Zyzzyva"` equals `"a-zyzzyva"`. The decoded bytes are compared in constant time, so the
+/// comparison does not leak where they first differ.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word of either string is not found in the
+/// dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// assert!(niceware::phrases_equal("a bioengineering", " A,\tBioengineering\n").unwrap());
+/// assert!(!niceware::phrases_equal("a bioengineering", "a").unwrap());
+/// ```
+pub fn phrases_equal(a: &str, b: &str) -> Result<bool, Error> {
+    let a = passphrase_to_bytes(&split_words(a).collect::<Vec<_>>())?;
+    let b = passphrase_to_bytes(&split_words(b).collect::<Vec<_>>())?;
+    Ok(constant_time_eq(&a, &b))
+}
+
+/// Check an entered passphrase against the bytes it should encode, tolerating a few wrong words.
+///
+/// Meant for flows where the user proves they wrote down their passphrase: a misspelled or wrong
+/// word counts as one mismatch, and the zero-based positions of all mismatches are returned so
+/// the user can be told exactly which words to re-check. Words not found in the dictionary count
+/// as mismatches rather than errors, and case is ignored.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if `expected` has an odd number of bytes, and a
+/// LengthMismatch error if the number of entered words differs from the number expected. It
+/// returns a TooManyMismatches error, which also lists the positions, if more than
+/// `max_word_errors` words are wrong.
+///
+/// ## Examples
+///
+/// ```
+/// let expected = [0, 0, 17, 212, 12, 140, 90, 246];
+/// let entered = ["a", "bioengineerign", "balloted", "gobbled"];
+/// assert_eq!(niceware::verify_approx(&expected, &entered, 1).unwrap(), &[1]);
+/// assert!(niceware::verify_approx(&expected, &entered, 0).is_err());
+/// ```
+pub fn verify_approx(
+    expected: &[u8],
+    entered: &[&str],
+    max_word_errors: usize,
+) -> Result<Vec<usize>, Error> {
+    if expected.len() % 2 != 0 {
+        return Err(Error::InvalidSize {
+            size: expected.len(),
+        });
+    }
+    if expected.len() / 2 != entered.len() {
+        return Err(Error::LengthMismatch {
+            expected: expected.len() / 2,
+            actual: entered.len(),
+        });
+    }
+
+    let positions: Vec<usize> = expected
+        .chunks_exact(2)
+        .zip(entered)
+        .enumerate()
+        .filter(|(_, (pair, word))| {
+            word_to_index(word).map_or(true, |index| index.to_be_bytes() != **pair)
+        })
+        .map(|(position, _)| position)
+        .collect();
+
+    if positions.len() > max_word_errors {
+        return Err(Error::TooManyMismatches {
+            positions,
+            max_mismatches: max_word_errors,
+        });
+    }
+    Ok(positions)
+}
+
+/// Compare bytes without returning early at the first difference.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{distance, phrases_equal, verify_approx, Distance, Error};
+
+    #[test]
+    fn differing_positions() {
+        let a = ["a", "bioengineering", "balloted", "gobbled"];
+        assert_eq!(distance(&a, &a).unwrap(), Distance::default());
+        assert!(
+            distance(&a, &["A", "Bioengineering", "BALLOTED", "gobbled"])
+                .unwrap()
+                .is_zero()
+        );
+
+        // "aah" is [0, 1] and "zyzzyva" is [255, 255]: one differing byte, then two.
+        let distance = distance(&a, &["aah", "bioengineering", "balloted", "zyzzyva"]).unwrap();
+        assert_eq!(distance.word_positions, &[0, 3]);
+        assert_eq!(distance.words(), 2);
+        assert_eq!(distance.differing_bytes, 3);
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            distance(&["a"], &["a", "a"]).unwrap_err().to_string(),
+            "expected length 1 but got 2"
+        );
+        assert_eq!(
+            distance(&["a"], &["ninetales"]).unwrap_err().to_string(),
+            "unknown word: ninetales"
+        );
+    }
+
+    #[test]
+    fn equal_across_formatting() {
+        let canonical = "a bioengineering balloted gobbled";
+        for formatted in [
+            "A BIOENGINEERING BALLOTED GOBBLED",
+            "  a\tbioengineering\n\nballoted gobbled  ",
+            "a, bioengineering, balloted, gobbled",
+            "a-bioengineering-balloted-gobbled",
+        ] {
+            assert!(phrases_equal(canonical, formatted).unwrap());
+        }
+        assert!(!phrases_equal(canonical, "a bioengineering balloted").unwrap());
+        assert!(!phrases_equal(canonical, "a bioengineering gobbled balloted").unwrap());
+        assert!(phrases_equal("", " , ").unwrap());
+        assert_eq!(
+            phrases_equal(canonical, "a ninetales")
+                .unwrap_err()
+                .to_string(),
+            "unknown word: ninetales"
+        );
+    }
+
+    #[test]
+    fn approximate_verification() {
+        let expected = [0, 0, 17, 212, 12, 140, 90, 246];
+        let exact = ["A", "bioengineering", "balloted", "gobbled"];
+        assert!(verify_approx(&expected, &exact, 0).unwrap().is_empty());
+
+        let typos = ["a", "bioengineerin", "balloted", "zyzzyva"];
+        assert_eq!(verify_approx(&expected, &typos, 2).unwrap(), &[1, 3]);
+        match verify_approx(&expected, &typos, 1).unwrap_err() {
+            Error::TooManyMismatches {
+                positions,
+                max_mismatches,
+            } => {
+                assert_eq!(positions, &[1, 3]);
+                assert_eq!(max_mismatches, 1);
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+        assert_eq!(
+            verify_approx(&expected, &typos, 0).unwrap_err().to_string(),
+            "2 words differ but at most 0 may: [1, 3]"
+        );
+
+        assert!(matches!(
+            verify_approx(&expected, &exact[..3], 4),
+            Err(Error::LengthMismatch {
+                expected: 4,
+                actual: 3
+            })
+        ));
+        assert!(matches!(
+            verify_approx(&[0], &[], 0),
+            Err(Error::InvalidSize { size: 1 })
+        ));
+    }
+}
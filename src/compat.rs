@@ -0,0 +1,144 @@
+//! Mirror of the original JavaScript API.
+//!
+//! The functions here behave like `bytesToPassphrase`, `passphraseToBytes` and
+//! `generatePassphrase` from [niceware](https://github.com/diracdeltas/niceware), including the
+//! error messages and `generate_passphrase` taking a number of bytes rather than words. They are
+//! meant for projects porting from Node that want to run differential tests against the original
+//! or migrate one call site at a time. Like the original, they always use the English wordlist,
+//! even if another one was registered with `set_default_wordlist`.
+
+#[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+use crate::EntropySource;
+use std::{error, fmt};
+
+/// Maximum size of a generated passphrase in bytes, as in the original.
+pub const MAX_PASSPHRASE_SIZE: usize = 1024;
+
+/// Error with the same message as the original implementation would throw.
+///
+/// The underlying crate error is available through `into_inner`.
+#[derive(Debug)]
+pub struct Error(crate::Error);
+
+impl Error {
+    /// Return the crate error this was created from.
+    pub fn into_inner(self) -> crate::Error {
+        self.0
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            crate::Error::InvalidSize { .. } => {
+                write!(f, "Only even-sized byte arrays are supported.")
+            }
+            crate::Error::UnknownWord { word } | crate::Error::NonAsciiInput { word, .. } => {
+                write!(f, "Invalid word: {}", word)
+            }
+            crate::Error::TooManyWords { max_words, .. } => {
+                write!(f, "Size must be between 0 and {} bytes.", max_words * 2)
+            }
+            inner => inner.fmt(f),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl From<crate::Error> for Error {
+    fn from(e: crate::Error) -> Self {
+        Error(e)
+    }
+}
+
+/// Equivalent of `bytesToPassphrase`.
+///
+/// ## Errors
+///
+/// This function returns an error if the given slice has an odd number of bytes.
+pub fn bytes_to_passphrase(bytes: &[u8]) -> Result<Vec<&'static str>, Error> {
+    Ok(crate::english_bytes_to_passphrase(bytes)?)
+}
+
+/// Equivalent of `passphraseToBytes`.
+///
+/// ## Errors
+///
+/// This function returns an error if a word is not found in the dictionary.
+pub fn passphrase_to_bytes(words: &[&str]) -> Result<Vec<u8>, Error> {
+    Ok(crate::english_passphrase_to_bytes(words)?)
+}
+
+/// Equivalent of `generatePassphrase`, taking the size in bytes.
+///
+/// ## Errors
+///
+/// This function returns an error if `size` is greater than `MAX_PASSPHRASE_SIZE`, if it is odd,
+/// or if the underlying RNG failed to generate bytes.
+#[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+pub fn generate_passphrase(size: usize) -> Result<Vec<&'static str>, Error> {
+    if size > MAX_PASSPHRASE_SIZE {
+        return Err(Error(crate::Error::TooManyWords {
+            num_words: size.div_ceil(2),
+            max_words: MAX_PASSPHRASE_SIZE / 2,
+        }));
+    }
+    if size % 2 != 0 {
+        return Err(Error(crate::Error::InvalidSize { size }));
+    }
+    let mut bytes = vec![0; size];
+    crate::entropy::system_rng().fill_entropy(&mut bytes)?;
+    Ok(crate::english_bytes_to_passphrase(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+    use crate::compat::generate_passphrase;
+    use crate::compat::{bytes_to_passphrase, passphrase_to_bytes};
+
+    #[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+    #[test]
+    fn generate_sizes() {
+        assert!(generate_passphrase(0).unwrap().is_empty());
+        assert_eq!(generate_passphrase(2).unwrap().len(), 1);
+        assert_eq!(generate_passphrase(1024).unwrap().len(), 512);
+        assert_eq!(
+            generate_passphrase(1025).unwrap_err().to_string(),
+            "Size must be between 0 and 1024 bytes."
+        );
+        assert_eq!(
+            generate_passphrase(23).unwrap_err().to_string(),
+            "Only even-sized byte arrays are supported."
+        );
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(
+            bytes_to_passphrase(&[1]).unwrap_err().to_string(),
+            "Only even-sized byte arrays are supported."
+        );
+        assert_eq!(
+            passphrase_to_bytes(&["You", "love", "ninetales"])
+                .unwrap_err()
+                .to_string(),
+            "Invalid word: ninetales"
+        );
+    }
+
+    #[test]
+    fn same_encoding() {
+        let bytes = [
+            0, 0, 17, 212, 12, 140, 90, 246, 46, 83, 254, 60, 54, 169, 255, 255,
+        ];
+        let words = bytes_to_passphrase(&bytes).unwrap();
+        assert_eq!(words, crate::bytes_to_passphrase(&bytes).unwrap());
+        assert_eq!(passphrase_to_bytes(&words).unwrap(), bytes);
+    }
+}
@@ -0,0 +1,433 @@
+use crate::generate::{check_num_words, random_index};
+use crate::{EntropySource, Error, Generated, WORD_COUNT};
+use std::collections::HashSet;
+
+/// A rule deciding whether two words are easily mistaken for each other.
+///
+/// Rules receive lowercase words from the wordlist. Closures taking two words and returning a
+/// `bool` are rules too, so callers can add their own next to `DEFAULT_CONFUSABLE_RULES`.
+pub trait ConfusableRule: Sync {
+    /// Whether `a` and `b` are easily mistaken for each other.
+    fn confusable(&self, a: &str, b: &str) -> bool;
+
+    /// Indices of the words `b` of the default wordlist for which `confusable(word, b)` holds, if
+    /// the rule can list them without testing every word.
+    ///
+    /// Generators call `confusable` once per word of the list when this returns `None`, which is
+    /// the default.
+    fn confusable_indices(&self, _word: &str) -> Option<Vec<u16>> {
+        None
+    }
+}
+
+impl<F: Fn(&str, &str) -> bool + Sync> ConfusableRule for F {
+    fn confusable(&self, a: &str, b: &str) -> bool {
+        self(a, b)
+    }
+}
+
+/// Words at most one insertion, deletion or substitution apart, such as "ballot" and "ballots",
+/// are confusable. A word is also confusable with itself.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WithinOneEdit;
+
+impl ConfusableRule for WithinOneEdit {
+    fn confusable(&self, a: &str, b: &str) -> bool {
+        let (a, b) = if a.len() <= b.len() {
+            (a.as_bytes(), b.as_bytes())
+        } else {
+            (b.as_bytes(), a.as_bytes())
+        };
+        if b.len() - a.len() > 1 {
+            return false;
+        }
+        let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+        if a.len() == b.len() {
+            // One substitution at `prefix`, or none at all.
+            a.get(prefix + 1..) == b.get(prefix + 1..)
+        } else {
+            // One insertion into `a` at `prefix`.
+            a[prefix..] == b[prefix + 1..]
+        }
+    }
+
+    /// Looks up every deletion, substitution and insertion of a lowercase letter. Words of a
+    /// custom default wordlist may use other letters, so this returns `None` for them.
+    fn confusable_indices(&self, word: &str) -> Option<Vec<u16>> {
+        if crate::wordlist::custom_default_wordlist().is_some() {
+            return None;
+        }
+
+        let word = word.as_bytes();
+        let mut edits = Vec::with_capacity(word.len() * 53 + 26);
+        for i in 0..=word.len() {
+            if i < word.len() {
+                edits.push([&word[..i], &word[i + 1..]].concat());
+            }
+            for letter in b'a'..=b'z' {
+                if i < word.len() {
+                    edits.push([&word[..i], &[letter], &word[i + 1..]].concat());
+                }
+                edits.push([&word[..i], &[letter], &word[i..]].concat());
+            }
+        }
+        Some(
+            edits
+                .iter()
+                .filter_map(|edit| std::str::from_utf8(edit).ok())
+                .filter_map(|edit| crate::word_to_index(edit).ok())
+                .collect(),
+        )
+    }
+}
+
+/// Words in the same group of a homophone table, such as "right" and "write", are confusable.
+#[derive(Debug, Clone, Copy)]
+pub struct Homophones(pub &'static [&'static [&'static str]]);
+
+impl ConfusableRule for Homophones {
+    fn confusable(&self, a: &str, b: &str) -> bool {
+        self.0
+            .iter()
+            .any(|group| group.contains(&a) && group.contains(&b))
+    }
+
+    fn confusable_indices(&self, word: &str) -> Option<Vec<u16>> {
+        let groups = self.0.iter().filter(|group| group.contains(&word));
+        Some(
+            groups
+                .flat_map(|group| group.iter())
+                .filter_map(|&other| {
+                    let index = crate::word_to_index(other).ok()?;
+                    // A lenient list may map the table's word to a different word of its own.
+                    (crate::index_to_word(index) == other).then_some(index)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Common English homophones that are all in the wordlist.
+pub const HOMOPHONES: &[&[&str]] = &[
+    &["there", "their"],
+    &["to", "too", "two"],
+    &["night", "knight"],
+    &["right", "write", "rite", "wright"],
+    &["hear", "here"],
+    &["know", "no"],
+    &["knew", "new", "gnu"],
+    &["sea", "see"],
+    &["son", "sun"],
+    &["one", "won"],
+    &["for", "four", "fore"],
+    &["by", "buy", "bye"],
+    &["eight", "ate"],
+    &["flour", "flower"],
+    &["mail", "male"],
+    &["pair", "pear", "pare"],
+    &["peace", "piece"],
+    &["plain", "plane"],
+    &["rain", "reign", "rein"],
+    &["road", "rode", "rowed"],
+    &["sail", "sale"],
+    &["tail", "tale"],
+    &["wait", "weight"],
+    &["weak", "week"],
+    &["wear", "where", "ware"],
+    &["which", "witch"],
+    &["wood", "would"],
+    &["hole", "whole"],
+    &["hour", "our"],
+    &["meat", "meet", "mete"],
+    &["bare", "bear"],
+    &["blew", "blue"],
+    &["brake", "break"],
+    &["scent", "sent"],
+    &["dear", "deer"],
+    &["die", "dye"],
+    &["fair", "fare"],
+    &["flew", "flu", "flue"],
+    &["groan", "grown"],
+    &["hair", "hare"],
+    &["heal", "heel"],
+    &["hi", "high"],
+    &["him", "hymn"],
+    &["horse", "hoarse"],
+    &["in", "inn"],
+    &["key", "quay"],
+    &["knot", "not"],
+    &["lead", "led"],
+    &["made", "maid"],
+    &["main", "mane"],
+    &["mind", "mined"],
+    &["missed", "mist"],
+    &["morning", "mourning"],
+    &["none", "nun"],
+    &["oar", "or", "ore"],
+    &["pail", "pale"],
+    &["pain", "pane"],
+    &["peak", "peek", "pique"],
+    &["principal", "principle"],
+    &["rap", "wrap"],
+    &["real", "reel"],
+    &["ring", "wring"],
+    &["role", "roll"],
+    &["root", "route"],
+    &["sew", "so", "sow"],
+    &["sight", "site", "cite"],
+    &["sole", "soul"],
+    &["some", "sum"],
+    &["stair", "stare"],
+    &["stake", "steak"],
+    &["steal", "steel"],
+    &["suite", "sweet"],
+    &["tide", "tied"],
+    &["toe", "tow"],
+    &["vain", "vane", "vein"],
+    &["waist", "waste"],
+    &["way", "weigh", "whey"],
+    &["weather", "whether"],
+    &["whine", "wine"],
+    &["aloud", "allowed"],
+    &["ball", "bawl"],
+    &["band", "banned"],
+    &["bean", "been"],
+    &["beat", "beet"],
+    &["berry", "bury"],
+    &["boarder", "border"],
+    &["bold", "bowled"],
+    &["bough", "bow"],
+    &["bridal", "bridle"],
+    &["cheap", "cheep"],
+    &["coarse", "course"],
+    &["colonel", "kernel"],
+    &["council", "counsel"],
+    &["creak", "creek"],
+    &["doe", "dough"],
+    &["dual", "duel"],
+    &["ewe", "you", "yew"],
+    &["faint", "feint"],
+    &["feat", "feet"],
+    &["find", "fined"],
+    &["fir", "fur"],
+    &["flea", "flee"],
+    &["gait", "gate"],
+    &["guessed", "guest"],
+    &["hay", "hey"],
+    &["heard", "herd"],
+    &["hoard", "horde"],
+    &["idle", "idol"],
+    &["isle", "aisle"],
+    &["jewel", "joule"],
+    &["knead", "need"],
+    &["lessen", "lesson"],
+    &["loan", "lone"],
+    &["manner", "manor"],
+    &["medal", "meddle"],
+    &["might", "mite"],
+    &["moose", "mousse"],
+    &["muscle", "mussel"],
+    &["naval", "navel"],
+    &["pedal", "peddle"],
+    &["plum", "plumb"],
+    &["pray", "prey"],
+    &["raise", "raze"],
+    &["read", "reed"],
+    &["rye", "wry"],
+    &["scene", "seen"],
+    &["seam", "seem"],
+    &["shoe", "shoo"],
+    &["side", "sighed"],
+    &["sign", "sine"],
+    &["slay", "sleigh"],
+    &["stationary", "stationery"],
+    &["straight", "strait"],
+    &["tea", "tee"],
+    &["threw", "through"],
+    &["throne", "thrown"],
+    &["tire", "tyre"],
+    &["vary", "very"],
+    &["wade", "weighed"],
+    &["war", "wore"],
+    &["warn", "worn"],
+    &["whirled", "world"],
+    &["yoke", "yolk"],
+];
+
+/// Rules used when none are given: `WithinOneEdit` and `Homophones(HOMOPHONES)`.
+pub const DEFAULT_CONFUSABLE_RULES: &[&dyn ConfusableRule] =
+    &[&WithinOneEdit, &Homophones(HOMOPHONES)];
+
+/// Generate a passphrase in which no two consecutive words are confusable under any of `rules`.
+///
+/// A word confusable with the one before it is redrawn, since such neighbours cause most
+/// transcription mix-ups. Each word after the first is then uniform over the words that are not
+/// confusable with its predecessor, and the reported entropy adds up `log2` of those counts; with
+/// the default rules that is a small fraction of a bit per word.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 512, a
+/// NoCandidateWords error if the rules leave no word that may follow a drawn word, and an RNGError
+/// if the RNG failed to generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// use niceware::{ConfusableRule, DEFAULT_CONFUSABLE_RULES};
+///
+/// let generated = niceware::generate_passphrase_without_confusables(
+///     6,
+///     DEFAULT_CONFUSABLE_RULES,
+///     &mut rand::thread_rng(),
+/// )
+/// .unwrap();
+/// for pair in generated.words.windows(2) {
+///     assert!(!DEFAULT_CONFUSABLE_RULES
+///         .iter()
+///         .any(|rule| rule.confusable(pair[0], pair[1])));
+/// }
+/// ```
+pub fn generate_passphrase_without_confusables<R: EntropySource>(
+    num_words: usize,
+    rules: &[&dyn ConfusableRule],
+    rng: &mut R,
+) -> Result<Generated, Error> {
+    check_num_words(num_words)?;
+
+    let mut chosen: Vec<&'static str> = Vec::with_capacity(num_words);
+    let mut entropy_bits = 0.0;
+    while chosen.len() < num_words {
+        let excluded = match chosen.last() {
+            Some(previous) => confusable_with(rules, previous),
+            None => HashSet::new(),
+        };
+        let allowed = WORD_COUNT - excluded.len();
+        if allowed == 0 {
+            return Err(Error::NoCandidateWords);
+        }
+        entropy_bits += (allowed as f64).log2();
+
+        loop {
+            let index = random_index(rng)?;
+            if !excluded.contains(&index) {
+                chosen.push(crate::index_to_word(index));
+                break;
+            }
+        }
+    }
+
+    Ok(Generated {
+        words: chosen,
+        entropy_bits,
+    })
+}
+
+/// Indices of the words of the default wordlist confusable with `word` under any of `rules`.
+fn confusable_with(rules: &[&dyn ConfusableRule], word: &str) -> HashSet<u16> {
+    let mut excluded = HashSet::new();
+    for rule in rules {
+        match rule.confusable_indices(word) {
+            Some(indices) => excluded.extend(indices),
+            None => excluded.extend(
+                (0..=u16::MAX).filter(|&index| rule.confusable(word, crate::index_to_word(index))),
+            ),
+        }
+    }
+    excluded
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        generate_passphrase_without_confusables, ConfusableRule, Error, Homophones, WithinOneEdit,
+        DEFAULT_CONFUSABLE_RULES, HOMOPHONES,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn edit_distance_rule() {
+        for (a, b) in [
+            ("ballot", "ballots"),
+            ("a", ""),
+            ("cat", "cat"),
+            ("cat", "cut"),
+            ("cat", "cast"),
+            ("cats", "cat"),
+            ("at", "cat"),
+        ] {
+            assert!(WithinOneEdit.confusable(a, b), "{} {}", a, b);
+        }
+        for (a, b) in [
+            ("cat", "dog"),
+            ("cat", "act"),
+            ("cat", "carts"),
+            ("cat", "c"),
+            ("ballots", "balloted"),
+        ] {
+            assert!(!WithinOneEdit.confusable(a, b), "{} {}", a, b);
+        }
+    }
+
+    #[test]
+    fn homophone_rule() {
+        let rule = Homophones(HOMOPHONES);
+        assert!(rule.confusable("write", "right"));
+        assert!(!rule.confusable("write", "hear"));
+        for word in HOMOPHONES.iter().flat_map(|group| group.iter()) {
+            crate::passphrase_to_bytes(&[word]).unwrap();
+        }
+    }
+
+    #[test]
+    fn listed_indices_match_rules() {
+        let rules: [&dyn ConfusableRule; 2] = [&WithinOneEdit, &Homophones(HOMOPHONES)];
+        for rule in rules {
+            for word in ["a", "ballot", "right", "zyzzyva", "stationery"] {
+                let mut listed = rule.confusable_indices(word).unwrap();
+                listed.sort_unstable();
+                listed.dedup();
+                let scanned: Vec<u16> = (0..=u16::MAX)
+                    .filter(|&index| rule.confusable(word, crate::index_to_word(index)))
+                    .collect();
+                assert_eq!(listed, scanned, "{}", word);
+            }
+        }
+
+        let generated = generate_passphrase_without_confusables(
+            512,
+            DEFAULT_CONFUSABLE_RULES,
+            &mut StdRng::seed_from_u64(5),
+        )
+        .unwrap();
+        assert_eq!(generated.words.len(), 512);
+        assert!(generated.entropy_bits > 511.0 * 16.0);
+    }
+
+    #[test]
+    fn no_confusable_neighbours() {
+        let mut rng = StdRng::seed_from_u64(5);
+        // Only words sharing a first letter with their neighbour are confusable here.
+        let same_initial = |a: &str, b: &str| a.as_bytes()[0] == b.as_bytes()[0];
+        let rules: [&dyn ConfusableRule; 2] = [DEFAULT_CONFUSABLE_RULES[0], &same_initial];
+        let generated = generate_passphrase_without_confusables(16, &rules, &mut rng).unwrap();
+        assert_eq!(generated.words.len(), 16);
+        for pair in generated.words.windows(2) {
+            assert_ne!(pair[0].as_bytes()[0], pair[1].as_bytes()[0]);
+        }
+        assert!(generated.entropy_bits < 16.0 * 16.0);
+        assert!(generated.entropy_bits > 16.0 * 15.0);
+
+        let everything = |_: &str, _: &str| true;
+        assert!(matches!(
+            generate_passphrase_without_confusables(2, &[&everything], &mut rng),
+            Err(Error::NoCandidateWords)
+        ));
+        assert_eq!(
+            generate_passphrase_without_confusables(1, &[&everything], &mut rng)
+                .unwrap()
+                .entropy_bits,
+            16.0
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use crate::{word_to_index, Error, MAX_PASSPHRASE_WORDS};
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+/// Hook rewriting every word before it is looked up.
+type Normalizer = Arc<dyn Fn(&str) -> Cow<'_, str> + Send + Sync>;
+
+/// Bounds on the input a `PhraseDecoder` accepts.
+///
+/// Services decoding phrases typed by untrusted users can bound the work and memory of every
+/// request: input beyond `max_input_bytes` is rejected before it is scanned, and a word is never
+/// buffered past `max_word_len` bytes. The default allows 512 words of up to 64 bytes and 64 KiB
+/// of text, which is generous for any real phrase, even with a normalizer rewriting words.
+///
+/// ## Examples
+///
+/// ```
+/// let limits = niceware::DecodeLimits {
+///     max_words: 24,
+///     ..Default::default()
+/// };
+/// assert_eq!(limits.decode("a zyzzyva").unwrap(), [0, 0, 255, 255]);
+/// assert!(limits.decode(&"a ".repeat(25)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecodeLimits {
+    /// Most words decoded; more is a TooManyWords error.
+    pub max_words: usize,
+    /// Longest word in bytes, before normalization; longer is a WordTooLong error.
+    pub max_word_len: usize,
+    /// Most bytes of text pushed in total; more is an InputTooLong error.
+    pub max_input_bytes: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        DecodeLimits {
+            max_words: MAX_PASSPHRASE_WORDS,
+            max_word_len: 64,
+            max_input_bytes: 64 * 1024,
+        }
+    }
+}
+
+impl DecodeLimits {
+    /// Decode a passphrase string within these limits.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an InputTooLong, WordTooLong or TooManyWords error if the text exceeds
+    /// a limit, and an UnknownWord error if a word is not found in the dictionary.
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>, Error> {
+        let mut decoder = PhraseDecoder::new().with_limits(*self);
+        decoder.push_text(text)?;
+        decoder.finish()
+    }
+}
+
+/// Incrementally decode a passphrase as words or text arrive.
+///
+/// Text can be pushed in arbitrary fragments, for example one keystroke at a time. Words are
+/// separated by whitespace, and a word cut off at the end of a fragment is buffered until the
+/// next fragment (or `finish`) completes it.
+///
+/// ## Examples
+///
+/// ```
+/// let mut decoder = niceware::PhraseDecoder::new();
+/// decoder.push_text("a bioengi").unwrap();
+/// assert_eq!(decoder.bytes(), &[0, 0]);
+/// decoder.push_text("neering zyz").unwrap();
+/// assert_eq!(decoder.partial_word(), "zyz");
+/// decoder.push_text("zyva").unwrap();
+/// assert_eq!(decoder.finish().unwrap(), &[0, 0, 17, 212, 255, 255]);
+/// ```
+#[derive(Clone, Default)]
+pub struct PhraseDecoder {
+    bytes: Vec<u8>,
+    partial: String,
+    normalizer: Option<Normalizer>,
+    limits: Option<DecodeLimits>,
+    input_len: usize,
+    /// Whether the rest of an overlong word is being discarded up to the next whitespace.
+    skipping: bool,
+}
+
+impl fmt::Debug for PhraseDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PhraseDecoder")
+            .field("bytes", &self.bytes)
+            .field("partial", &self.partial)
+            .field("normalizer", &self.normalizer.as_ref().map(|_| ".."))
+            .field("limits", &self.limits)
+            .field("skipping", &self.skipping)
+            .finish()
+    }
+}
+
+impl PhraseDecoder {
+    /// Create an empty decoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rewrite every word with `normalizer` before looking it up.
+    ///
+    /// The hook sees each complete word as typed and can strip diacritics, expand abbreviations or
+    /// map aliases; returning `Cow::Borrowed` leaves the word unchanged without allocating. Case is
+    /// still ignored after the hook runs. An unknown word is reported as typed, not as rewritten.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    ///
+    /// let mut decoder = niceware::PhraseDecoder::new().with_normalizer(|word| match word {
+    ///     "zz" => Cow::Borrowed("zyzzyva"),
+    ///     word => Cow::Owned(word.replace('é', "e")),
+    /// });
+    /// decoder.push_text("zz creneléd").unwrap();
+    /// assert_eq!(decoder.finish().unwrap(), &[255, 255, 46, 83]);
+    /// ```
+    pub fn with_normalizer<F>(mut self, normalizer: F) -> Self
+    where
+        F: Fn(&str) -> Cow<'_, str> + Send + Sync + 'static,
+    {
+        self.normalizer = Some(Arc::new(normalizer));
+        self
+    }
+
+    /// Enforce `limits` on everything pushed from now on.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Decode a single complete word.
+    ///
+    /// This does not touch a partial word buffered by `push_text`.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an UnknownWord error if the word is not found in the dictionary, and
+    /// a WordTooLong or TooManyWords error if it exceeds the limits. The decoder is left unchanged
+    /// in those cases.
+    pub fn push_word(&mut self, word: &str) -> Result<(), Error> {
+        self.check_word_len(word.len())?;
+        self.check_words()?;
+        let word_index = self.lookup(word)?;
+        self.bytes.extend(&word_index.to_be_bytes());
+        Ok(())
+    }
+
+    /// Decode a fragment of text.
+    ///
+    /// Every word terminated by whitespace is decoded. Trailing characters are kept as a partial
+    /// word and prefixed to the next fragment.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an UnknownWord error as soon as a completed word is not found in the
+    /// dictionary. That word is discarded and the remainder of `text` is not consumed. The same
+    /// goes for a WordTooLong or TooManyWords error when a word exceeds the limits; after a
+    /// WordTooLong error, the rest of the overlong word is discarded up to the next whitespace, even
+    /// across fragments. An InputTooLong error is returned before any of `text` is consumed.
+    pub fn push_text(&mut self, text: &str) -> Result<(), Error> {
+        if let Some(limits) = self.limits {
+            let len = self.input_len.saturating_add(text.len());
+            if len > limits.max_input_bytes {
+                return Err(Error::InputTooLong {
+                    len,
+                    max_len: limits.max_input_bytes,
+                });
+            }
+            self.input_len = len;
+        }
+
+        let mut rest = text;
+        while let Some(end) = rest.find(char::is_whitespace) {
+            self.push_partial(&rest[..end])?;
+            let separator_len = rest[end..].chars().next().map_or(1, char::len_utf8);
+            rest = &rest[end + separator_len..];
+            self.flush_partial()?;
+        }
+        self.push_partial(rest)
+    }
+
+    /// Bytes decoded so far, excluding any partial word.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Characters of the word currently being typed.
+    pub fn partial_word(&self) -> &str {
+        &self.partial
+    }
+
+    /// Decode any buffered partial word and return all bytes.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an UnknownWord error if the buffered partial word is not found in the
+    /// dictionary, and a TooManyWords error if decoding it would exceed the limit on words. A word
+    /// already cut off with a WordTooLong error by `push_text` is discarded, not reported again.
+    pub fn finish(mut self) -> Result<Vec<u8>, Error> {
+        self.flush_partial()?;
+        Ok(self.bytes)
+    }
+
+    fn push_partial(&mut self, text: &str) -> Result<(), Error> {
+        if self.skipping {
+            return Ok(());
+        }
+        let result = self.check_word_len(self.partial.len() + text.len());
+        if result.is_err() {
+            self.partial.clear();
+            self.skipping = true;
+        } else {
+            self.partial.push_str(text);
+        }
+        result
+    }
+
+    fn flush_partial(&mut self) -> Result<(), Error> {
+        self.skipping = false;
+        if self.partial.is_empty() {
+            return Ok(());
+        }
+        let result = self.check_words().and_then(|()| self.lookup(&self.partial));
+        self.partial.clear();
+        self.bytes.extend(&result?.to_be_bytes());
+        Ok(())
+    }
+
+    fn check_word_len(&self, len: usize) -> Result<(), Error> {
+        match self.limits {
+            Some(limits) if len > limits.max_word_len => Err(Error::WordTooLong {
+                len,
+                max_len: limits.max_word_len,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_words(&self) -> Result<(), Error> {
+        match self.limits {
+            Some(limits) if self.bytes.len() / 2 >= limits.max_words => Err(Error::TooManyWords {
+                num_words: self.bytes.len() / 2 + 1,
+                max_words: limits.max_words,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn lookup(&self, word: &str) -> Result<u16, Error> {
+        match &self.normalizer {
+            Some(normalizer) => word_to_index(&normalizer(word)).map_err(|e| match e {
+                Error::UnknownWord { .. } => Error::UnknownWord {
+                    word: word.to_string(),
+                },
+                e => e,
+            }),
+            None => word_to_index(word),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DecodeLimits, Error, PhraseDecoder};
+    use std::borrow::Cow;
+
+    #[test]
+    fn text_in_fragments() {
+        let text = "a bioengineering  balloted\tgobbled\ncreneled written depriving zyzzyva";
+        let expected = [
+            0, 0, 17, 212, 12, 140, 90, 246, 46, 83, 254, 60, 54, 169, 255, 255,
+        ];
+
+        for split in 0..text.len() {
+            let mut decoder = PhraseDecoder::new();
+            decoder.push_text(&text[..split]).unwrap();
+            decoder.push_text(&text[split..]).unwrap();
+            assert_eq!(decoder.finish().unwrap(), &expected);
+        }
+    }
+
+    #[test]
+    fn partial_word_is_buffered() {
+        let mut decoder = PhraseDecoder::new();
+        decoder.push_text("zyzzyva A").unwrap();
+        assert_eq!(decoder.bytes(), &[255, 255]);
+        assert_eq!(decoder.partial_word(), "A");
+        decoder.push_text("ah ").unwrap();
+        assert_eq!(decoder.partial_word(), "");
+        assert_eq!(decoder.bytes(), &[255, 255, 0, 1]);
+    }
+
+    #[test]
+    fn unknown_word() {
+        let mut decoder = PhraseDecoder::new();
+        assert_eq!(
+            decoder.push_text("a ninetales a").unwrap_err().to_string(),
+            "unknown word: ninetales"
+        );
+        assert_eq!(decoder.bytes(), &[0, 0]);
+        assert_eq!(decoder.partial_word(), "");
+
+        decoder.push_word("zyzzyva").unwrap();
+        decoder.push_text("ninetal").unwrap();
+        assert_eq!(
+            decoder.finish().unwrap_err().to_string(),
+            "unknown word: ninetal"
+        );
+    }
+
+    #[test]
+    fn normalizer_hook() {
+        let mut decoder = PhraseDecoder::new().with_normalizer(|word| {
+            Cow::Owned(word.trim_matches(|c: char| !c.is_alphabetic()).to_string())
+        });
+        decoder.push_text("(a) \"zyzzyva\", ").unwrap();
+        assert_eq!(decoder.bytes(), &[0, 0, 255, 255]);
+
+        let mut copy = decoder.clone();
+        copy.push_word("zyzzyva!").unwrap();
+        assert!(format!("{:?}", copy).contains("normalizer"));
+        assert_eq!(
+            decoder.push_word("nine-tales").unwrap_err().to_string(),
+            "unknown word: nine-tales"
+        );
+        assert!(PhraseDecoder::new().push_word("zyzzyva!").is_err());
+    }
+
+    #[test]
+    fn limits() {
+        let limits = DecodeLimits {
+            max_words: 2,
+            max_word_len: 8,
+            max_input_bytes: 20,
+        };
+        assert_eq!(limits.decode("a zyzzyva ").unwrap(), [0, 0, 255, 255]);
+        assert!(matches!(
+            limits.decode("a a a"),
+            Err(Error::TooManyWords {
+                num_words: 3,
+                max_words: 2
+            })
+        ));
+        assert_eq!(
+            limits.decode("a bioengineering").unwrap_err().to_string(),
+            "word of 14 bytes cannot be longer than 8"
+        );
+        assert_eq!(
+            limits.decode(&" ".repeat(21)).unwrap_err().to_string(),
+            "input of 21 bytes cannot be longer than 20"
+        );
+
+        // A long word is cut off while it is typed, its rest is dropped up to the next
+        // whitespace, and the input counts across fragments.
+        let mut decoder = PhraseDecoder::new().with_limits(limits);
+        decoder.push_text("a bioeng").unwrap();
+        assert!(matches!(
+            decoder.push_text("ineer"),
+            Err(Error::WordTooLong { len: 11, .. })
+        ));
+        assert_eq!(decoder.partial_word(), "");
+        decoder.push_text("ing").unwrap();
+        assert_eq!(decoder.partial_word(), "");
+        decoder.push_text(" ").unwrap();
+        assert_eq!(decoder.bytes(), &[0, 0]);
+        assert!(matches!(
+            decoder.push_text("    "),
+            Err(Error::InputTooLong { len: 21, .. })
+        ));
+        assert!(decoder.push_word("zyzzyvas").is_err());
+        decoder.push_word("zyzzyva").unwrap();
+        assert!(matches!(
+            decoder.push_word("a"),
+            Err(Error::TooManyWords { .. })
+        ));
+        decoder.push_text("a").unwrap();
+        assert_eq!(
+            decoder.finish().unwrap_err().to_string(),
+            "number of words 3 cannot be greater than 2"
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use crate::generate::check_num_words;
+use crate::mac::mac_words;
+use crate::{bytes_to_passphrase, passphrase_to_bytes, Error};
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// Label mixed into every rotation HMAC, so the codes differ from other uses of the same secret.
+const ROTATION_LABEL: &[u8] = b"niceware next_phrase";
+
+/// Derive the phrase number `counter` of a rotating sequence from a master secret.
+///
+/// The phrase is the leading `num_words * 2` bytes of HMAC-SHA256(`master`, label || `counter` as
+/// big-endian u64). Phrases for different counters are independent, and knowing some of them
+/// reveals nothing about the others or the master, so a system rotating verbal access codes, for
+/// example with the month number as the counter, only needs to store the master.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 16.
+///
+/// ## Examples
+///
+/// ```
+/// let master = [7; 32];
+/// let january = niceware::next_phrase(&master, 1, 3).unwrap();
+/// assert_eq!(january, niceware::next_phrase(&master, 1, 3).unwrap());
+/// assert_ne!(january, niceware::next_phrase(&master, 2, 3).unwrap());
+/// ```
+pub fn next_phrase(
+    master: &[u8],
+    counter: u64,
+    num_words: usize,
+) -> Result<Vec<&'static str>, Error> {
+    mac_words(master, &[ROTATION_LABEL, &counter.to_be_bytes()], num_words)
+}
+
+/// Salt of every HKDF step of `derive_child`.
+const CHILD_SALT: &[u8] = b"niceware derive_child";
+
+/// Derive a child phrase from a master phrase along a path of indices.
+///
+/// Every index is one HKDF-SHA256 step: the parent's bytes are the input key material and the
+/// index, as a big-endian u32, is the info. The child has as many words as the master, so it is
+/// as strong, and the derivation is hierarchical: the child at `[a, b]` is the child at `[b]` of
+/// the child at `[a]`, so a device given the phrase at `[a]` can derive everything below it but
+/// nothing above or beside it. An empty path returns the master itself.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if the master phrase has more than 512 words, and an
+/// UnknownWord error if one of its words is not found in the dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// let master = ["a", "bioengineering", "balloted", "gobbled"];
+/// let laptop = niceware::derive_child(&master, &[0, 1]).unwrap();
+/// assert_eq!(laptop.len(), 4);
+/// let devices = niceware::derive_child(&master, &[0]).unwrap();
+/// assert_eq!(niceware::derive_child(&devices, &[1]).unwrap(), laptop);
+/// ```
+pub fn derive_child(master: &[&str], path: &[u32]) -> Result<Vec<&'static str>, Error> {
+    check_num_words(master.len())?;
+    let mut key = passphrase_to_bytes(master)?;
+    for index in path {
+        let mut child = vec![0; key.len()];
+        Hkdf::<Sha256>::new(Some(CHILD_SALT), &key)
+            .expand(&index.to_be_bytes(), &mut child)
+            .expect("a passphrase is short enough for HKDF output");
+        key = child;
+    }
+    bytes_to_passphrase(&key)
+}
+
+/// Length of a YubiKey HMAC-SHA1 challenge-response response.
+const CHALLENGE_RESPONSE_LEN: usize = 20;
+
+/// Salt of the HKDF step of `challenge_response_phrase`.
+const CHALLENGE_RESPONSE_SALT: &[u8] = b"niceware challenge_response";
+
+/// Derive a phrase from a YubiKey HMAC-SHA1 challenge-response, so it never has to be stored.
+///
+/// Send a memorized challenge to the key's challenge-response slot, for example with
+/// `ykchalresp -2 -x <challenge>` or a USB HID library, and pass the 20-byte response here. The
+/// same key and challenge always give the same phrase. The phrase is HKDF-SHA256 output with the
+/// response as input key material, so it can be any length; past 10 words it is no stronger than
+/// the 160-bit response.
+///
+/// ## Errors
+///
+/// This function returns a LengthMismatch error if `response` is not 20 bytes long, and a
+/// TooManyWords error if `num_words` is greater than 512.
+///
+/// ## Examples
+///
+/// ```
+/// let response = [0x5a; 20];
+/// let words = niceware::challenge_response_phrase(&response, 8).unwrap();
+/// assert_eq!(words, niceware::challenge_response_phrase(&response, 8).unwrap());
+/// ```
+pub fn challenge_response_phrase(
+    response: &[u8],
+    num_words: usize,
+) -> Result<Vec<&'static str>, Error> {
+    if response.len() != CHALLENGE_RESPONSE_LEN {
+        return Err(Error::LengthMismatch {
+            expected: CHALLENGE_RESPONSE_LEN,
+            actual: response.len(),
+        });
+    }
+    check_num_words(num_words)?;
+    let mut bytes = vec![0; num_words * 2];
+    Hkdf::<Sha256>::new(Some(CHALLENGE_RESPONSE_SALT), response)
+        .expand(&[], &mut bytes)
+        .expect("a passphrase is short enough for HKDF output");
+    bytes_to_passphrase(&bytes)
+}
+
+/// Number of PBKDF2 rounds of `salted_passphrase_to_bytes`, as in BIP39.
+pub const SALT_ROUNDS: u32 = 2048;
+
+/// Decode a passphrase, mixing in an extra secret so the words alone are not enough.
+///
+/// This mirrors the optional passphrase of BIP39: the bytes are PBKDF2-HMAC-SHA256 with the
+/// passphrase's bytes as password, `"niceware"` followed by the UTF-8 bytes of `extra` as salt, and
+/// `SALT_ROUNDS` rounds, truncated to the length of the passphrase's bytes. A stolen paper copy of
+/// the words then needs the extra word or PIN as well. Every `extra`, including the empty string,
+/// gives valid but different bytes, so a mistyped extra cannot be detected; `extra` is used as
+/// given, without case or Unicode normalization.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if the passphrase has more than 512 words, and an
+/// UnknownWord error if one of its words is not found in the dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// let words = ["a", "bioengineering", "balloted", "gobbled"];
+/// let key = niceware::salted_passphrase_to_bytes(&words, "1234").unwrap();
+/// assert_eq!(key.len(), 8);
+/// assert_ne!(key, niceware::salted_passphrase_to_bytes(&words, "1235").unwrap());
+/// ```
+pub fn salted_passphrase_to_bytes(words: &[&str], extra: &str) -> Result<Vec<u8>, Error> {
+    check_num_words(words.len())?;
+    let password = passphrase_to_bytes(words)?;
+    let salt = [&b"niceware"[..], extra.as_bytes()].concat();
+    let mut bytes = vec![0; password.len()];
+    pbkdf2::pbkdf2_hmac::<Sha256>(&password, &salt, SALT_ROUNDS, &mut bytes);
+    Ok(bytes)
+}
+
+/// Memory cost of `site_password` in KiB, OWASP's minimum for Argon2id.
+pub const SITE_PASSWORD_MEMORY_KIB: u32 = 19 * 1024;
+
+/// Number of Argon2id passes of `site_password`.
+pub const SITE_PASSWORD_PASSES: u32 = 2;
+
+/// Salt prefix and HKDF salt of `site_password`.
+const SITE_PASSWORD_LABEL: &[u8] = b"niceware site_password";
+
+/// Derive the password for a site from a master phrase, like a stateless password manager.
+///
+/// The master phrase's bytes are stretched with Argon2id, using `SITE_PASSWORD_MEMORY_KIB` of
+/// memory and `SITE_PASSWORD_PASSES` passes, salted with a label, the UTF-8 bytes of `site`
+/// prefixed with their length, and `counter`. The 32-byte result is expanded with HKDF-SHA256 to
+/// `num_words` words. Nothing but the master has to be remembered: the same site and counter
+/// always give the same phrase, a leaked site password reveals nothing about the master or other
+/// sites, and the memory-hard step makes guessing the master from one expensive. Bump `counter`
+/// to rotate a single site's password. `site` is used as given, so normalize it (for example to
+/// a lowercase domain) before calling.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if the master phrase or `num_words` is greater than
+/// 512, and an UnknownWord error if a word of the master phrase is not found in the dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// let master = ["a", "bioengineering", "balloted", "gobbled", "creneled", "written"];
+/// let words = niceware::site_password(&master, "example.com", 0, 5).unwrap();
+/// assert_eq!(words.len(), 5);
+/// ```
+pub fn site_password(
+    master: &[&str],
+    site: &str,
+    counter: u32,
+    num_words: usize,
+) -> Result<Vec<&'static str>, Error> {
+    check_num_words(master.len())?;
+    check_num_words(num_words)?;
+    let password = passphrase_to_bytes(master)?;
+
+    let mut salt = SITE_PASSWORD_LABEL.to_vec();
+    salt.extend(&(site.len() as u64).to_be_bytes());
+    salt.extend(site.as_bytes());
+    salt.extend(&counter.to_be_bytes());
+
+    let params = argon2::Params::new(SITE_PASSWORD_MEMORY_KIB, SITE_PASSWORD_PASSES, 1, Some(32))
+        .expect("site password parameters are valid");
+    let mut key = [0; 32];
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+        .hash_password_into(&password, &salt, &mut key)
+        .expect("site password inputs are within Argon2 limits");
+
+    let mut bytes = vec![0; num_words * 2];
+    Hkdf::<Sha256>::new(Some(SITE_PASSWORD_LABEL), &key)
+        .expand(&[], &mut bytes)
+        .expect("a passphrase is short enough for HKDF output");
+    bytes_to_passphrase(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mac::mac_words;
+    use crate::{
+        challenge_response_phrase, derive_child, next_phrase, salted_passphrase_to_bytes,
+        site_password, Error,
+    };
+
+    #[test]
+    fn rotation_sequence() {
+        let master = [1; 16];
+        let phrases: Vec<_> = (0..12)
+            .map(|month| next_phrase(&master, month, 4).unwrap())
+            .collect();
+        for (i, a) in phrases.iter().enumerate() {
+            assert_eq!(a.len(), 4);
+            assert!(phrases[i + 1..].iter().all(|b| a != b));
+        }
+        assert_eq!(next_phrase(&master, 3, 2).unwrap(), &phrases[3][..2]);
+        assert_ne!(next_phrase(&[2; 16], 3, 4).unwrap(), phrases[3]);
+        // The label keeps rotation phrases apart from one-time codes of the same secret.
+        assert_ne!(
+            next_phrase(&master, 3, 4).unwrap(),
+            mac_words(&master, &[&3u64.to_be_bytes()], 4).unwrap()
+        );
+        assert!(next_phrase(&master, 0, 17).is_err());
+    }
+
+    #[test]
+    fn child_derivation() {
+        let master = ["A", "bioengineering", "balloted", "gobbled"];
+        assert_eq!(
+            derive_child(&master, &[]).unwrap(),
+            ["a", "bioengineering", "balloted", "gobbled"]
+        );
+
+        let child = derive_child(&master, &[7]).unwrap();
+        assert_eq!(child.len(), 4);
+        assert_ne!(child, derive_child(&master, &[8]).unwrap());
+        assert_ne!(child, derive_child(&master[..2], &[7]).unwrap()[..2]);
+        assert_eq!(
+            derive_child(&master, &[7, 0, 3]).unwrap(),
+            derive_child(&derive_child(&child, &[0]).unwrap(), &[3]).unwrap()
+        );
+        assert_ne!(
+            derive_child(&master, &[7, 0]).unwrap(),
+            derive_child(&master, &[0, 7]).unwrap()
+        );
+
+        // The longest passphrase still fits in one HKDF output.
+        let long = vec!["zyzzyva"; 512];
+        assert_eq!(derive_child(&long, &[1]).unwrap().len(), 512);
+        assert!(derive_child(&["ninetales"], &[1]).is_err());
+        assert!(derive_child(&vec!["a"; 513], &[1]).is_err());
+    }
+
+    #[test]
+    fn salted_bytes() {
+        let words = ["a", "bioengineering", "balloted", "gobbled"];
+        let plain = salted_passphrase_to_bytes(&words, "").unwrap();
+        assert_eq!(plain.len(), 8);
+        assert_ne!(plain, crate::passphrase_to_bytes(&words).unwrap());
+        assert_ne!(plain, salted_passphrase_to_bytes(&words, "pin").unwrap());
+        assert_eq!(
+            salted_passphrase_to_bytes(&["A", "BIOENGINEERING", "balloted", "gobbled"], "pin")
+                .unwrap(),
+            salted_passphrase_to_bytes(&words, "pin").unwrap()
+        );
+        assert!(salted_passphrase_to_bytes(&[], "pin").unwrap().is_empty());
+        assert!(salted_passphrase_to_bytes(&["ninetales"], "pin").is_err());
+    }
+
+    #[test]
+    fn challenge_response() {
+        let response = [1; 20];
+        let phrase = challenge_response_phrase(&response, 12).unwrap();
+        assert_eq!(phrase.len(), 12);
+        assert_eq!(
+            challenge_response_phrase(&response, 4).unwrap(),
+            phrase[..4]
+        );
+        assert_ne!(challenge_response_phrase(&[2; 20], 12).unwrap(), phrase);
+        assert_eq!(
+            challenge_response_phrase(&response, 512).unwrap().len(),
+            512
+        );
+        assert!(matches!(
+            challenge_response_phrase(&[1; 19], 4),
+            Err(Error::LengthMismatch {
+                expected: 20,
+                actual: 19
+            })
+        ));
+        assert!(challenge_response_phrase(&response, 513).is_err());
+    }
+
+    #[test]
+    fn site_passwords() {
+        let master = ["a", "bioengineering", "balloted", "gobbled"];
+        let site = site_password(&master, "example.com", 0, 6).unwrap();
+        assert_eq!(site.len(), 6);
+        assert_eq!(
+            site_password(
+                &["A", "Bioengineering", "balloted", "gobbled"],
+                "example.com",
+                0,
+                6
+            )
+            .unwrap(),
+            site
+        );
+        assert_eq!(
+            site_password(&master, "example.com", 0, 3).unwrap(),
+            &site[..3]
+        );
+        assert_ne!(site_password(&master, "example.com", 1, 6).unwrap(), site);
+        assert_ne!(site_password(&master, "example.org", 0, 6).unwrap(), site);
+        assert_ne!(
+            site_password(&master[..3], "example.com", 0, 6).unwrap(),
+            site
+        );
+        assert!(site_password(&master, "", 0, 0).unwrap().is_empty());
+        assert!(matches!(
+            site_password(&master, "example.com", 0, 513),
+            Err(Error::TooManyWords { .. })
+        ));
+        assert!(matches!(
+            site_password(&["bulbasaur"], "example.com", 0, 6),
+            Err(Error::UnknownWord { .. })
+        ));
+    }
+}
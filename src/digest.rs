@@ -0,0 +1,167 @@
+use crate::{bytes_to_passphrase, Error};
+use sha2::{Digest, Sha256};
+use std::io::{self, Read};
+
+/// Number of words in the passphrase of a full SHA-256 digest.
+pub const DIGEST_WORDS: usize = 16;
+
+/// Hash everything read from `reader` with SHA-256 and return the passphrase of the digest.
+///
+/// The full digest is 16 words. Passing a smaller `num_words` keeps only the leading words, which
+/// is enough to compare files by ear but gives up collision resistance accordingly: `n` words
+/// carry `16 * n` bits.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 16, and an Io error
+/// if reading fails.
+///
+/// ## Examples
+///
+/// ```
+/// let words = niceware::digest_reader_to_passphrase(&b"hello"[..], Some(4)).unwrap();
+/// assert_eq!(words.len(), 4);
+/// ```
+pub fn digest_reader_to_passphrase<R: Read>(
+    mut reader: R,
+    num_words: Option<usize>,
+) -> Result<Vec<&'static str>, Error> {
+    let num_words = num_words.unwrap_or(DIGEST_WORDS);
+    if num_words > DIGEST_WORDS {
+        return Err(Error::TooManyWords {
+            num_words,
+            max_words: DIGEST_WORDS,
+        });
+    }
+
+    let mut hasher = Sha256::new();
+    io::copy(&mut reader, &mut hasher)?;
+    let digest = hasher.finalize();
+    bytes_to_passphrase(&digest[..num_words * 2])
+}
+
+/// Probability that some two of `population` random phrases of `num_words` words are equal.
+///
+/// Meant for choosing the length of short identifiers and truncated fingerprints: it uses the
+/// birthday bound `1 - exp(-n(n - 1) / 2N)` for `n` uniformly random phrases out of `N = 2^(16 *
+/// num_words)`, which is accurate whenever collisions are unlikely, and is exactly 1 when there
+/// are more phrases than distinct values.
+///
+/// ## Examples
+///
+/// ```
+/// // A million two-word IDs almost surely collide; four words make it about 1 in 37 million.
+/// assert!(niceware::collision_probability(2, 1_000_000) > 0.99);
+/// assert!(niceware::collision_probability(4, 1_000_000) < 3e-8);
+/// ```
+pub fn collision_probability(num_words: usize, population: u64) -> f64 {
+    if num_words < 4 && population > 1 << (16 * num_words) {
+        return 1.0;
+    }
+    let bits = 16.0 * num_words as f64;
+    let n = population as f64;
+    let pairs = n * (n - 1.0) / 2.0;
+    // pairs / N, through logarithms so that N = 2^bits does not overflow.
+    let expected = (pairs.log2() - bits).exp2();
+    -(-expected).exp_m1()
+}
+
+/// Fewest words for which `population` random phrases collide with probability at most `risk`.
+///
+/// This inverts `collision_probability`.
+///
+/// ## Panics
+///
+/// Panics if `risk` is not between 0 and 1.
+///
+/// ## Examples
+///
+/// ```
+/// assert_eq!(niceware::words_needed_for_collision_risk(1_000_000, 1e-6), 4);
+/// assert_eq!(niceware::words_needed_for_collision_risk(1, 0.0), 0);
+/// ```
+pub fn words_needed_for_collision_risk(population: u64, risk: f64) -> usize {
+    assert!(
+        (0.0..=1.0).contains(&risk),
+        "collision risk {} must be between 0 and 1",
+        risk
+    );
+    (0..)
+        .find(|&num_words| collision_probability(num_words, population) <= risk)
+        .expect("the probability reaches zero")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bytes_to_passphrase, collision_probability, digest_reader_to_passphrase,
+        words_needed_for_collision_risk,
+    };
+    use std::io::{self, Read};
+
+    #[test]
+    fn known_digest() {
+        // SHA-256 of the empty string starts with e3b0c442.
+        let words = digest_reader_to_passphrase(io::empty(), None).unwrap();
+        assert_eq!(words.len(), 16);
+        assert_eq!(
+            &words[..2],
+            bytes_to_passphrase(&[0xe3, 0xb0, 0xc4, 0x42]).unwrap()
+        );
+        assert_eq!(
+            digest_reader_to_passphrase(io::empty(), Some(2)).unwrap(),
+            &words[..2]
+        );
+        assert!(digest_reader_to_passphrase(io::empty(), Some(0))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            digest_reader_to_passphrase(io::empty(), Some(17))
+                .unwrap_err()
+                .to_string(),
+            "number of words 17 cannot be greater than 16"
+        );
+
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::Other, "disk on fire"))
+            }
+        }
+        let err = digest_reader_to_passphrase(Failing, None).unwrap_err();
+        assert_eq!(err.to_string(), "failed to read input: disk on fire");
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn birthday_bound() {
+        // About 77,163 values of 32 bits give even odds of a collision.
+        assert!((collision_probability(2, 77_163) - 0.5).abs() < 1e-4);
+        assert_eq!(collision_probability(1, 0), 0.0);
+        assert_eq!(collision_probability(1, 1), 0.0);
+        assert_eq!(collision_probability(0, 2), 1.0);
+        assert_eq!(collision_probability(1, 65_537), 1.0);
+        assert!(collision_probability(1, 65_536) > 0.99);
+        assert!(collision_probability(16, u64::MAX) < 1e-38);
+        assert_eq!(collision_probability(512, u64::MAX), 0.0);
+        // 16 * num_words does not fit in an i32 here.
+        assert_eq!(collision_probability(1 << 28, u64::MAX), 0.0);
+        assert_eq!(collision_probability(usize::MAX, u64::MAX), 0.0);
+
+        assert_eq!(words_needed_for_collision_risk(0, 0.0), 0);
+        assert_eq!(words_needed_for_collision_risk(2, 1.0), 0);
+        assert_eq!(words_needed_for_collision_risk(1_000, 0.01), 2);
+        assert_eq!(words_needed_for_collision_risk(1_000_000, 1e-6), 4);
+        assert_eq!(words_needed_for_collision_risk(u64::MAX, 1e-30), 15);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be between 0 and 1")]
+    fn risk_out_of_range() {
+        words_needed_for_collision_risk(10, 1.5);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::Error;
+
+/// Source of the randomness that passphrases and shares are drawn from.
+///
+/// Every RNG from `rand` 0.8 that implements `RngCore` and `CryptoRng` is an entropy source. With
+/// the `rand09` feature, RNGs from `rand` 0.9 can be used by wrapping them in `Rand09`, so this
+/// crate does not hold back the `rand` version of its users.
+pub trait EntropySource {
+    /// Fill `dest` with random bytes.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an RNGError if the source failed to generate bytes.
+    fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), Error>;
+}
+
+impl<R: rand::RngCore + rand::CryptoRng + ?Sized> EntropySource for R {
+    fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        Ok(self.try_fill_bytes(dest)?)
+    }
+}
+
+/// Adapter making a `rand` 0.9 RNG an entropy source, as in `Rand09(rand::rng())`.
+#[cfg(feature = "rand09")]
+#[derive(Debug, Clone)]
+pub struct Rand09<R>(pub R);
+
+#[cfg(feature = "rand09")]
+impl<R: rand_core_09::CryptoRng> EntropySource for Rand09<R> {
+    fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Entropy source drawing from the RNG of a PKCS#11 token, such as an HSM.
+///
+/// For organizations whose policy requires key material to come from a hardware token rather
+/// than the host OS. Open and log in to a session with `cryptoki` as usual, then pass it to any
+/// generator:
+///
+/// ```no_run
+/// use cryptoki::context::{CInitializeArgs, CInitializeFlags, Pkcs11};
+///
+/// let pkcs11 = Pkcs11::new("/usr/lib/softhsm/libsofthsm2.so").unwrap();
+/// pkcs11
+///     .initialize(CInitializeArgs::new(CInitializeFlags::OS_LOCKING_OK))
+///     .unwrap();
+/// let slot = pkcs11.get_slots_with_token().unwrap()[0];
+/// let session = pkcs11.open_ro_session(slot).unwrap();
+/// let words = niceware::generate_passphrase_with(8, &mut niceware::Pkcs11Rng(&session)).unwrap();
+/// ```
+#[cfg(feature = "pkcs11")]
+#[derive(Debug)]
+pub struct Pkcs11Rng<'a>(pub &'a cryptoki::session::Session);
+
+#[cfg(feature = "pkcs11")]
+impl EntropySource for Pkcs11Rng<'_> {
+    fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0
+            .generate_random_slice(dest)
+            .map_err(|e| Error::Pkcs11 { inner: e.into() })
+    }
+}
+
+/// Entropy source drawing from ring's `SystemRandom`, enabled by the `ring-rng` feature.
+///
+/// For projects that already depend on ring and would rather not pull in another CSPRNG. With
+/// the feature enabled, `generate_passphrase` uses it too.
+///
+/// ```
+/// let words = niceware::generate_passphrase_with(8, &mut niceware::RingRng::default()).unwrap();
+/// assert_eq!(words.len(), 8);
+/// ```
+#[cfg(feature = "ring-rng")]
+#[derive(Debug, Clone)]
+pub struct RingRng(pub ring::rand::SystemRandom);
+
+#[cfg(feature = "ring-rng")]
+impl Default for RingRng {
+    fn default() -> Self {
+        RingRng(ring::rand::SystemRandom::new())
+    }
+}
+
+#[cfg(feature = "ring-rng")]
+impl EntropySource for RingRng {
+    fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        use ring::rand::SecureRandom;
+
+        self.0
+            .fill(dest)
+            .map_err(|e| Error::Ring { inner: e.into() })
+    }
+}
+
+/// The RNG of `generate_passphrase`.
+#[cfg(feature = "ring-rng")]
+pub(crate) fn system_rng() -> impl EntropySource {
+    RingRng::default()
+}
+
+/// The RNG of `generate_passphrase`.
+#[cfg(all(feature = "os-rng", not(feature = "ring-rng")))]
+pub(crate) fn system_rng() -> impl EntropySource {
+    rand::thread_rng()
+}
+
+#[cfg(all(test, feature = "rand09"))]
+mod tests {
+    use crate::{generate_passphrase_excluding, EntropySource, Rand09};
+
+    /// Counter standing in for a `rand` 0.9 RNG.
+    struct Counter(u8);
+
+    impl rand_core_09::RngCore for Counter {
+        fn next_u32(&mut self) -> u32 {
+            rand_core_09::impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            rand_core_09::impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                *byte = self.0;
+                self.0 = self.0.wrapping_add(1);
+            }
+        }
+    }
+
+    impl rand_core_09::CryptoRng for Counter {}
+
+    #[test]
+    fn rand09_adapter() {
+        let mut rng = Rand09(Counter(0));
+        let mut bytes = [0; 4];
+        rng.fill_entropy(&mut bytes).unwrap();
+        assert_eq!(bytes, [0, 1, 2, 3]);
+
+        let generated = generate_passphrase_excluding(1, &[], &mut rng).unwrap();
+        assert_eq!(
+            generated.words,
+            crate::bytes_to_passphrase(&[4, 5]).unwrap()
+        );
+    }
+}
@@ -1,6 +1,11 @@
-use std::{error, fmt};
+use std::{error, fmt, io};
 
+/// Errors returned by this crate.
+///
+/// New variants may be added in minor releases. Every variant has a stable numeric `code` and
+/// string `code_name` for FFI and machine-readable output; codes are never reused.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Error returned when an array size is of odd length.
     InvalidSize { size: usize },
@@ -10,6 +15,165 @@ pub enum Error {
     TooManyWords { num_words: usize, max_words: usize },
     /// Error returned when an RNG fails to generate entropy.
     RNGError { inner: rand::Error },
+    /// Error returned when the check word of one or more word groups does not match.
+    ChecksumMismatch { groups: Vec<usize> },
+    /// Error returned when a secret sharing threshold is zero or larger than the number of shares.
+    InvalidThreshold { threshold: u8, shares: u8 },
+    /// Error returned when fewer shares than the threshold are given to recover a secret.
+    NotEnoughShares { needed: u8, got: usize },
+    /// Error returned when shares do not belong to the same split.
+    InconsistentShares,
+    /// Error returned when a header is missing or does not match the words that follow it.
+    InvalidHeader,
+    /// Error returned when a payload is too long to describe in a header.
+    PayloadTooLarge { size: usize, max_size: usize },
+    /// Error returned when another implementation's wordlist digest differs from this crate's.
+    IncompatibleWordlist,
+    /// Error returned when two inputs that must have matching lengths do not.
+    LengthMismatch { expected: usize, actual: usize },
+    /// Error returned when a symbol table is too small or too large for a radix codec.
+    InvalidSymbolCount { count: usize },
+    /// Error returned when a symbol is not part of a radix codec's symbol table.
+    UnknownSymbol { position: usize },
+    /// Error returned when symbols encode a value that does not fit in the requested bytes.
+    Overflow { size: usize },
+    /// Error returned when restrictions on generation leave no words to choose from.
+    NoCandidateWords,
+    /// Error returned when no generated passphrase passed a filter within the allowed attempts.
+    FilterExhausted { attempts: usize },
+    /// Error returned when text is not a minisign or signify key file.
+    InvalidKeyFile,
+    /// Error returned when text is not a valid multibase string or CID.
+    InvalidMultibase,
+    /// Error returned when reading input fails.
+    Io { inner: io::Error },
+    /// Error returned when dictated text does not follow the dictation template.
+    InvalidDictation { offset: usize },
+    /// Error returned when an entered passphrase has more wrong words than allowed.
+    TooManyMismatches {
+        positions: Vec<usize>,
+        max_mismatches: usize,
+    },
+    /// Error returned when the platform's secure storage fails.
+    Keyring {
+        inner: Box<dyn error::Error + Send + Sync>,
+    },
+    /// Error returned when a password cannot be made to fit a password policy. `requirement` is
+    /// the first one missed: `min_len`, `max_len`, `digit`, `symbol` or `uppercase`.
+    PolicyViolation {
+        len: usize,
+        requirement: &'static str,
+    },
+    /// Error returned when a word contains a character outside ASCII, such as a Cyrillic `а` that
+    /// looks like a Latin `a`. `fold_lookalikes` maps the common ones to ASCII.
+    NonAsciiInput { word: String, character: char },
+    /// Error returned when a PKCS#11 token fails to generate entropy.
+    Pkcs11 {
+        inner: Box<dyn error::Error + Send + Sync>,
+    },
+    /// Error returned when ring's system RNG fails to generate bytes.
+    Ring {
+        inner: Box<dyn error::Error + Send + Sync>,
+    },
+    /// Error returned when a known-answer test of `self_test` fails.
+    SelfTestFailed { check: &'static str },
+    /// Error returned when text to decode is longer than `DecodeLimits` allow.
+    InputTooLong { len: usize, max_len: usize },
+    /// Error returned when a word to decode is longer than `DecodeLimits` allow.
+    WordTooLong { len: usize, max_len: usize },
+    /// Error returned when a default wordlist is registered a second time.
+    DefaultWordlistAlreadySet,
+    /// Error returned when words are to be decoded with any of an empty set of wordlists.
+    NoWordlists,
+    /// Error returned when a template lacks a placeholder it needs, such as `{word}`.
+    InvalidTemplate { missing: &'static str },
+    /// Error returned when a code is checked against more time steps than allowed.
+    TooMuchSkew {
+        skew_steps: u64,
+        max_skew_steps: u64,
+    },
+}
+
+impl Error {
+    /// Stable numeric code of the error, starting at 1.
+    pub fn code(&self) -> u16 {
+        match self {
+            Error::InvalidSize { .. } => 1,
+            Error::UnknownWord { .. } => 2,
+            Error::TooManyWords { .. } => 3,
+            Error::RNGError { .. } => 4,
+            Error::ChecksumMismatch { .. } => 5,
+            Error::InvalidThreshold { .. } => 6,
+            Error::NotEnoughShares { .. } => 7,
+            Error::InconsistentShares => 8,
+            Error::InvalidHeader => 9,
+            Error::PayloadTooLarge { .. } => 10,
+            Error::IncompatibleWordlist => 11,
+            Error::LengthMismatch { .. } => 12,
+            Error::InvalidSymbolCount { .. } => 13,
+            Error::UnknownSymbol { .. } => 14,
+            Error::Overflow { .. } => 15,
+            Error::NoCandidateWords => 16,
+            Error::FilterExhausted { .. } => 17,
+            Error::InvalidKeyFile => 18,
+            Error::InvalidMultibase => 19,
+            Error::Io { .. } => 20,
+            Error::InvalidDictation { .. } => 21,
+            Error::TooManyMismatches { .. } => 22,
+            Error::Keyring { .. } => 23,
+            Error::PolicyViolation { .. } => 24,
+            Error::Pkcs11 { .. } => 25,
+            Error::NonAsciiInput { .. } => 26,
+            Error::Ring { .. } => 27,
+            Error::SelfTestFailed { .. } => 28,
+            Error::InputTooLong { .. } => 29,
+            Error::WordTooLong { .. } => 30,
+            Error::DefaultWordlistAlreadySet => 31,
+            Error::NoWordlists => 32,
+            Error::InvalidTemplate { .. } => 33,
+            Error::TooMuchSkew { .. } => 34,
+        }
+    }
+
+    /// Stable snake_case name of the error, such as `"unknown_word"`.
+    pub fn code_name(&self) -> &'static str {
+        match self {
+            Error::InvalidSize { .. } => "invalid_size",
+            Error::UnknownWord { .. } => "unknown_word",
+            Error::TooManyWords { .. } => "too_many_words",
+            Error::RNGError { .. } => "rng_error",
+            Error::ChecksumMismatch { .. } => "checksum_mismatch",
+            Error::InvalidThreshold { .. } => "invalid_threshold",
+            Error::NotEnoughShares { .. } => "not_enough_shares",
+            Error::InconsistentShares => "inconsistent_shares",
+            Error::InvalidHeader => "invalid_header",
+            Error::PayloadTooLarge { .. } => "payload_too_large",
+            Error::IncompatibleWordlist => "incompatible_wordlist",
+            Error::LengthMismatch { .. } => "length_mismatch",
+            Error::InvalidSymbolCount { .. } => "invalid_symbol_count",
+            Error::UnknownSymbol { .. } => "unknown_symbol",
+            Error::Overflow { .. } => "overflow",
+            Error::NoCandidateWords => "no_candidate_words",
+            Error::FilterExhausted { .. } => "filter_exhausted",
+            Error::InvalidKeyFile => "invalid_key_file",
+            Error::InvalidMultibase => "invalid_multibase",
+            Error::Io { .. } => "io",
+            Error::InvalidDictation { .. } => "invalid_dictation",
+            Error::TooManyMismatches { .. } => "too_many_mismatches",
+            Error::Keyring { .. } => "keyring",
+            Error::PolicyViolation { .. } => "policy_violation",
+            Error::Pkcs11 { .. } => "pkcs11",
+            Error::NonAsciiInput { .. } => "non_ascii_input",
+            Error::Ring { .. } => "ring",
+            Error::SelfTestFailed { .. } => "self_test_failed",
+            Error::InputTooLong { .. } => "input_too_long",
+            Error::WordTooLong { .. } => "word_too_long",
+            Error::DefaultWordlistAlreadySet => "default_wordlist_already_set",
+            Error::NoWordlists => "no_wordlists",
+            Error::InvalidTemplate { .. } => "invalid_template",
+            Error::TooMuchSkew { .. } => "too_much_skew",
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -17,6 +181,12 @@ impl fmt::Display for Error {
         match self {
             Error::InvalidSize { size } => write!(f, "odd size not supported: {}", size),
             Error::UnknownWord { word } => write!(f, "unknown word: {}", word),
+            Error::NonAsciiInput { word, character } => write!(
+                f,
+                "non-ASCII character U+{:04X} in word: {}",
+                u32::from(*character),
+                word
+            ),
             Error::TooManyWords {
                 num_words,
                 max_words,
@@ -30,6 +200,91 @@ impl fmt::Display for Error {
             Error::RNGError { inner } => {
                 write!(f, "failed to generate entropy for passphrase: {}", inner)
             }
+            Error::ChecksumMismatch { groups } => {
+                write!(f, "checksum mismatch in groups: {:?}", groups)
+            }
+            Error::InvalidThreshold { threshold, shares } => write!(
+                f,
+                "threshold {} must be between 1 and number of shares {}",
+                threshold, shares
+            ),
+            Error::NotEnoughShares { needed, got } => {
+                write!(f, "{} shares needed but only {} given", needed, got)
+            }
+            Error::InconsistentShares => write!(f, "shares do not belong to the same secret"),
+            Error::InvalidHeader => write!(f, "invalid passphrase header"),
+            Error::IncompatibleWordlist => write!(f, "wordlist does not match upstream"),
+            Error::LengthMismatch { expected, actual } => {
+                write!(f, "expected length {} but got {}", expected, actual)
+            }
+            Error::InvalidSymbolCount { count } => {
+                write!(f, "symbol table size not supported: {}", count)
+            }
+            Error::UnknownSymbol { position } => {
+                write!(f, "unknown symbol at position {}", position)
+            }
+            Error::Overflow { size } => {
+                write!(f, "symbols do not fit in {} bytes", size)
+            }
+            Error::NoCandidateWords => write!(f, "no words left to choose from"),
+            Error::FilterExhausted { attempts } => {
+                write!(
+                    f,
+                    "no passphrase passed the filter in {} attempts",
+                    attempts
+                )
+            }
+            Error::InvalidKeyFile => write!(f, "not a minisign or signify key file"),
+            Error::InvalidMultibase => write!(f, "invalid multibase string or CID"),
+            Error::Io { inner } => write!(f, "failed to read input: {}", inner),
+            Error::TooManyMismatches {
+                positions,
+                max_mismatches,
+            } => write!(
+                f,
+                "{} words differ but at most {} may: {:?}",
+                positions.len(),
+                max_mismatches,
+                positions
+            ),
+            Error::Keyring { inner } => write!(f, "keyring access failed: {}", inner),
+            Error::Pkcs11 { inner } => {
+                write!(f, "failed to generate entropy on PKCS#11 token: {}", inner)
+            }
+            Error::Ring { inner } => write!(f, "ring failed to generate random bytes: {}", inner),
+            Error::SelfTestFailed { check } => write!(f, "self-test failed: {}", check),
+            Error::DefaultWordlistAlreadySet => write!(f, "default wordlist is already set"),
+            Error::NoWordlists => write!(f, "no wordlist to decode with"),
+            Error::InvalidTemplate { missing } => write!(f, "template must contain {}", missing),
+            Error::TooMuchSkew {
+                skew_steps,
+                max_skew_steps,
+            } => write!(
+                f,
+                "skew of {} time steps cannot be greater than {}",
+                skew_steps, max_skew_steps
+            ),
+            Error::InputTooLong { len, max_len } => write!(
+                f,
+                "input of {} bytes cannot be longer than {}",
+                len, max_len
+            ),
+            Error::WordTooLong { len, max_len } => {
+                write!(f, "word of {} bytes cannot be longer than {}", len, max_len)
+            }
+            Error::PolicyViolation { len, requirement } => write!(
+                f,
+                "password of length {} does not meet the {} requirement of the policy",
+                len, requirement
+            ),
+            Error::InvalidDictation { offset } => {
+                write!(f, "dictation does not match template at byte {}", offset)
+            }
+            Error::PayloadTooLarge { size, max_size } => write!(
+                f,
+                "payload size {} cannot be greater than {}",
+                size, max_size
+            ),
         }
     }
 }
@@ -37,7 +292,12 @@ impl fmt::Display for Error {
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
+            #[cfg(feature = "os-rng")]
             Error::RNGError { ref inner } => Some(inner),
+            Error::Io { ref inner } => Some(inner),
+            Error::Keyring { ref inner }
+            | Error::Pkcs11 { ref inner }
+            | Error::Ring { ref inner } => Some(inner.as_ref()),
             _ => None,
         }
     }
@@ -48,3 +308,35 @@ impl From<rand::Error> for Error {
         Error::RNGError { inner: e }
     }
 }
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io { inner: e }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+    use std::error::Error as _;
+    use std::io;
+
+    #[test]
+    fn codes_and_sources() {
+        let err = Error::UnknownWord {
+            word: "ninetales".to_string(),
+        };
+        assert_eq!((err.code(), err.code_name()), (2, "unknown_word"));
+        assert!(err.source().is_none());
+
+        let err = Error::from(io::Error::new(io::ErrorKind::Other, "disk on fire"));
+        assert_eq!((err.code(), err.code_name()), (20, "io"));
+        assert_eq!(err.source().unwrap().to_string(), "disk on fire");
+
+        let err = Error::Keyring {
+            inner: "locked".into(),
+        };
+        assert_eq!(err.code(), 23);
+        assert_eq!(err.source().unwrap().to_string(), "locked");
+    }
+}
@@ -0,0 +1,594 @@
+use crate::pronounce::is_vowel;
+use crate::{
+    bytes_to_passphrase, index_to_word, pair_to_word, pronunciation_difficulty, split_words,
+    word_to_index, Error,
+};
+
+/// Rewrite a passphrase string into its canonical form: lowercase words separated by single spaces.
+///
+/// Input may use any case and any whitespace, commas or hyphens between words. Two strings that
+/// decode to the same bytes always have the same canonical form, so it can serve as a key for
+/// storage and deduplication.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// assert_eq!(
+///     niceware::canonicalize("  A,Bioengineering\tZYZZYVA ").unwrap(),
+///     "a bioengineering zyzzyva"
+/// );
+/// ```
+pub fn canonicalize(passphrase: &str) -> Result<String, Error> {
+    let mut canonical = String::with_capacity(passphrase.len());
+    for word in split_words(passphrase) {
+        if !canonical.is_empty() {
+            canonical.push(' ');
+        }
+        canonical.push_str(index_to_word(word_to_index(word)?));
+    }
+    Ok(canonical)
+}
+
+/// Create a URL-safe slug from given bytes: lowercase words joined with hyphens.
+///
+/// Every word is `[a-z]+`, so the slug can be used as is in URLs and filenames.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes.
+///
+/// ## Examples
+///
+/// ```
+/// assert_eq!(niceware::bytes_to_slug(&[0, 0, 255, 255]).unwrap(), "a-zyzzyva");
+/// assert_eq!(niceware::slug_to_bytes("a-zyzzyva").unwrap(), [0, 0, 255, 255]);
+/// ```
+pub fn bytes_to_slug(bytes: &[u8]) -> Result<String, Error> {
+    Ok(bytes_to_passphrase(bytes)?.join("-"))
+}
+
+/// Decode a slug created by `bytes_to_slug` into bytes.
+///
+/// Words are matched ignoring ASCII case, and like any passphrase string the slug may also be
+/// separated by whitespace or commas.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary.
+pub fn slug_to_bytes(slug: &str) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(slug.len() / 4);
+    for word in split_words(slug) {
+        bytes.extend(&word_to_index(word)?.to_be_bytes());
+    }
+    Ok(bytes)
+}
+
+/// Template used by `dictate` unless another one is given.
+pub const DEFAULT_DICTATION_TEMPLATE: &str = "word {n} of {total}: {word}.";
+
+/// Spell out a passphrase for reading aloud, one template instance per word.
+///
+/// In the template, `{n}` is replaced with the one-based position of the word, `{total}` with the
+/// number of words and `{word}` with the word itself. Instances are separated by single spaces.
+///
+/// ## Errors
+///
+/// This function returns an InvalidTemplate error if the template does not contain `{word}`.
+///
+/// ## Examples
+///
+/// ```
+/// use niceware::DEFAULT_DICTATION_TEMPLATE;
+///
+/// assert_eq!(
+///     niceware::dictate(&["a", "zyzzyva"], DEFAULT_DICTATION_TEMPLATE).unwrap(),
+///     "word 1 of 2: a. word 2 of 2: zyzzyva."
+/// );
+/// ```
+pub fn dictate(words: &[&str], template: &str) -> Result<String, Error> {
+    let pieces = dictation_pieces(template)?;
+    let total = words.len().to_string();
+    let mut dictation = String::new();
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            dictation.push(' ');
+        }
+        for piece in &pieces {
+            match piece {
+                Piece::Literal(literal) => dictation.push_str(literal),
+                Piece::Position => dictation.push_str(&(i + 1).to_string()),
+                Piece::Total => dictation.push_str(&total),
+                Piece::Word => dictation.push_str(word),
+                Piece::Index | Piece::Hex => unreachable!(),
+            }
+        }
+    }
+    Ok(dictation)
+}
+
+/// Decode text produced by `dictate` with the same template into bytes.
+///
+/// The text is matched against the template ignoring ASCII case and whitespace, so a transcript
+/// typed by the listener works as well. Positions and totals must be consistent, which catches a
+/// skipped or repeated word.
+///
+/// ## Errors
+///
+/// This function returns an InvalidDictation error with the byte offset of the first mismatch if
+/// the text does not follow the template or a position or total is wrong. It returns an
+/// UnknownWord error if a word is not found in the dictionary, and an InvalidTemplate error if the
+/// template does not contain `{word}`.
+pub fn parse_dictation(text: &str, template: &str) -> Result<Vec<u8>, Error> {
+    let pieces = dictation_pieces(template)?;
+    let mut bytes = Vec::new();
+    let mut total = None;
+    let mut rest = text.trim_start();
+
+    while !rest.is_empty() {
+        let position = bytes.len() / 2 + 1;
+        for piece in &pieces {
+            rest = rest.trim_start();
+            let offset = text.len() - rest.len();
+            match piece {
+                Piece::Literal(literal) => {
+                    for expected in literal.chars().filter(|c| !c.is_whitespace()) {
+                        rest = rest.trim_start();
+                        let mut chars = rest.chars();
+                        match chars.next() {
+                            Some(c) if c.eq_ignore_ascii_case(&expected) => rest = chars.as_str(),
+                            _ => {
+                                return Err(Error::InvalidDictation {
+                                    offset: text.len() - rest.len(),
+                                })
+                            }
+                        }
+                    }
+                }
+                Piece::Position | Piece::Total => {
+                    let (number, tail) = split_leading(rest, |c| c.is_ascii_digit());
+                    let expected = match piece {
+                        Piece::Position => Some(position),
+                        _ => *total.get_or_insert(number.parse().ok()),
+                    };
+                    if number.is_empty() || number.parse().ok() != expected {
+                        return Err(Error::InvalidDictation { offset });
+                    }
+                    rest = tail;
+                }
+                Piece::Word => {
+                    let (word, tail) = split_leading(rest, |c| c.is_ascii_alphabetic());
+                    if word.is_empty() {
+                        return Err(Error::InvalidDictation { offset });
+                    }
+                    bytes.extend(&word_to_index(word)?.to_be_bytes());
+                    rest = tail;
+                }
+                Piece::Index | Piece::Hex => unreachable!(),
+            }
+        }
+        rest = rest.trim_start();
+    }
+
+    if total.is_some_and(|total| total != Some(bytes.len() / 2)) {
+        return Err(Error::InvalidDictation { offset: text.len() });
+    }
+    Ok(bytes)
+}
+
+/// Format words for a screen reader, announcing positions and hinting at syllables of long words.
+///
+/// Every word is read as in `dictate` with `DEFAULT_DICTATION_TEMPLATE`. Words longer than six
+/// letters or hard to pronounce by `pronunciation_difficulty` are followed by their syllables in
+/// parentheses, such as "(gob-bled)", so a listener can transcribe a rare word one chunk at a
+/// time. The syllables come from a simple English heuristic and only serve as a hint.
+///
+/// ## Examples
+///
+/// ```
+/// assert_eq!(
+///     niceware::screen_reader_text(&["a", "bioengineering"]),
+///     "word 1 of 2: a. word 2 of 2: bioengineering (bi-o-en-gi-neer-ing)."
+/// );
+/// ```
+pub fn screen_reader_text(words: &[&str]) -> String {
+    let total = words.len();
+    let mut text = String::new();
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            text.push(' ');
+        }
+        text.push_str(&format!("word {} of {}: {}", i + 1, total, word));
+        let syllables = syllables(word);
+        if syllables.len() > 1 && (word.len() > 6 || pronunciation_difficulty(word) > 0) {
+            text.push_str(&format!(" ({})", syllables.join("-")));
+        }
+        text.push('.');
+    }
+    text
+}
+
+/// Decode text produced by `screen_reader_text` into bytes.
+///
+/// Parenthesized syllable hints are removed and the rest is parsed like `parse_dictation` with
+/// `DEFAULT_DICTATION_TEMPLATE`.
+///
+/// ## Errors
+///
+/// This function returns an InvalidDictation error if the text does not follow the format or a
+/// position or total is wrong; its offset counts bytes of the text with the hints removed. It
+/// returns an UnknownWord error if a word is not found in the dictionary.
+pub fn parse_screen_reader_text(text: &str) -> Result<Vec<u8>, Error> {
+    let mut stripped = String::with_capacity(text.len());
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+    parse_dictation(&stripped, DEFAULT_DICTATION_TEMPLATE)
+}
+
+/// Split an ASCII word into syllables, one vowel sound each.
+///
+/// Vowels next to each other are one sound if they form a digraph such as `ea` or `ou`, and are
+/// split otherwise, as in "bi-o"; a `y` before a vowel is a consonant, as in "ka-yak". A single
+/// consonant between vowel sounds starts the next syllable, and longer runs are split after their
+/// first consonant, keeping `ck` before and `ch`, `ph`, `sh`, `th` and `wh` after the split. The
+/// suffixes `ing`, and `ed` after `t` or `d`, are syllables of their own that leave the stem's
+/// consonants in place, as in "neer-ing", unless the stem doubled its last one, as in "run-ning".
+/// A final `e` after a consonant is silent. Words outside ASCII are not split.
+fn syllables(word: &str) -> Vec<&str> {
+    let letters = word.as_bytes();
+    if !word.is_ascii() {
+        return vec![word];
+    }
+    let lower = word.to_ascii_lowercase();
+    let suffix = if lower.ends_with("ing") {
+        Some(word.len() - 3)
+    } else if lower.ends_with("ted") || lower.ends_with("ded") {
+        Some(word.len() - 2)
+    } else {
+        None
+    };
+
+    let vowel = |i: usize| {
+        let before_vowel = i + 1 < letters.len() && is_vowel(letters, i + 1);
+        is_vowel(letters, i) && !(lower.as_bytes()[i] == b'y' && before_vowel)
+    };
+
+    let mut groups = Vec::new();
+    let mut i = 0;
+    while i < letters.len() {
+        if vowel(i) {
+            let start = i;
+            i += 1;
+            while i < letters.len() && vowel(i) && !vowel_break(&lower, i, suffix) {
+                i += 1;
+            }
+            groups.push(start..i);
+        } else {
+            i += 1;
+        }
+    }
+    let silent_e = groups.last().is_some_and(|last| {
+        last.start + 1 == letters.len() && letters[last.start].eq_ignore_ascii_case(&b'e')
+    });
+    if silent_e && groups.len() > 1 {
+        groups.pop();
+    }
+
+    let mut syllables = Vec::with_capacity(groups.len());
+    let mut start = 0;
+    for pair in groups.windows(2) {
+        let (end, next) = (pair[0].end, pair[1].start);
+        let cluster = word[end..next].to_ascii_lowercase();
+        let doubled = cluster.len() == 2 && cluster.as_bytes()[0] == cluster.as_bytes()[1];
+        let split = match (cluster.len(), cluster.get(..2)) {
+            _ if Some(next) == suffix && !doubled => next,
+            (2, Some("ck")) => next,
+            (2, Some("ch" | "ph" | "sh" | "th" | "wh")) | (0 | 1, _) => end,
+            (_, Some("ch" | "ck" | "ph" | "sh" | "th" | "wh")) => end + 2,
+            _ => end + 1,
+        };
+        syllables.push(&word[start..split]);
+        start = split;
+    }
+    syllables.push(&word[start..]);
+    syllables
+}
+
+/// Whether the vowel at `i` of a lowercase word starts a new vowel sound after the vowel before it.
+fn vowel_break(lower: &str, i: usize, suffix: Option<usize>) -> bool {
+    Some(i) == suffix
+        || !matches!(
+            &lower[i - 1..=i],
+            "ai" | "au"
+                | "ay"
+                | "ea"
+                | "ee"
+                | "ei"
+                | "eu"
+                | "ey"
+                | "ie"
+                | "oa"
+                | "oi"
+                | "oo"
+                | "ou"
+                | "oy"
+                | "ue"
+                | "ui"
+        )
+}
+
+/// Format bytes as words laid out by a template, one template instance per word.
+///
+/// The template can contain `{word}`, `{i}` for the zero-based index of the word, `{n}` for its
+/// one-based position, `{total}` for the number of words, and `{hex}` for the two bytes the word
+/// encodes as four lowercase hex digits. Instances are joined with `separator`. This gives scripts
+/// and label printers exactly the layout they need.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes.
+///
+/// ## Examples
+///
+/// ```
+/// assert_eq!(
+///     niceware::format_passphrase(&[0, 0, 255, 255], "{n}. {word} ({hex})", "\n").unwrap(),
+///     "1. a (0000)\n2. zyzzyva (ffff)"
+/// );
+/// ```
+pub fn format_passphrase(bytes: &[u8], template: &str, separator: &str) -> Result<String, Error> {
+    if bytes.len() % 2 != 0 {
+        return Err(Error::InvalidSize { size: bytes.len() });
+    }
+    let pieces = template_pieces(template, &PLACEHOLDERS);
+    let total = (bytes.len() / 2).to_string();
+    let mut formatted = String::new();
+    for (i, pair) in bytes.chunks_exact(2).enumerate() {
+        if i > 0 {
+            formatted.push_str(separator);
+        }
+        for piece in &pieces {
+            match piece {
+                Piece::Literal(literal) => formatted.push_str(literal),
+                Piece::Index => formatted.push_str(&i.to_string()),
+                Piece::Position => formatted.push_str(&(i + 1).to_string()),
+                Piece::Total => formatted.push_str(&total),
+                Piece::Word => formatted.push_str(pair_to_word(pair)),
+                Piece::Hex => formatted.push_str(&format!("{:02x}{:02x}", pair[0], pair[1])),
+            }
+        }
+    }
+    Ok(formatted)
+}
+
+/// Placeholders of `format_passphrase`; dictation uses the first three.
+const PLACEHOLDERS: [&str; 5] = ["{n}", "{total}", "{word}", "{i}", "{hex}"];
+
+/// Part of a template.
+enum Piece<'a> {
+    Literal(&'a str),
+    Position,
+    Total,
+    Word,
+    Index,
+    Hex,
+}
+
+fn dictation_pieces(template: &str) -> Result<Vec<Piece<'_>>, Error> {
+    let pieces = template_pieces(template, &PLACEHOLDERS[..3]);
+    if !pieces.iter().any(|piece| matches!(piece, Piece::Word)) {
+        return Err(Error::InvalidTemplate { missing: "{word}" });
+    }
+    Ok(pieces)
+}
+
+fn template_pieces<'a>(template: &'a str, placeholders: &[&str]) -> Vec<Piece<'a>> {
+    let mut pieces = Vec::new();
+    let mut rest = template;
+    while !rest.is_empty() {
+        let next = placeholders
+            .iter()
+            .filter_map(|placeholder| rest.find(placeholder).map(|at| (at, *placeholder)))
+            .min();
+        let (at, placeholder) = next.unwrap_or((rest.len(), ""));
+        if at > 0 {
+            pieces.push(Piece::Literal(&rest[..at]));
+        }
+        pieces.extend(match placeholder {
+            "{n}" => Some(Piece::Position),
+            "{total}" => Some(Piece::Total),
+            "{word}" => Some(Piece::Word),
+            "{i}" => Some(Piece::Index),
+            "{hex}" => Some(Piece::Hex),
+            _ => None,
+        });
+        rest = &rest[at + placeholder.len()..];
+    }
+    pieces
+}
+
+fn split_leading(text: &str, accept: impl Fn(char) -> bool) -> (&str, &str) {
+    text.split_at(text.find(|c| !accept(c)).unwrap_or(text.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::syllables;
+    use crate::{
+        bytes_to_passphrase, bytes_to_slug, canonicalize, dictate, format_passphrase,
+        parse_dictation, parse_screen_reader_text, screen_reader_text, slug_to_bytes, Error,
+        DEFAULT_DICTATION_TEMPLATE,
+    };
+
+    #[test]
+    fn canonical_form() {
+        assert_eq!(canonicalize("").unwrap(), "");
+        assert_eq!(canonicalize(" \n").unwrap(), "");
+        assert_eq!(
+            canonicalize("a-bioengineering--Balloted,gobbled").unwrap(),
+            "a bioengineering balloted gobbled"
+        );
+        let canonical = canonicalize("Zyzzyva A").unwrap();
+        assert_eq!(canonicalize(&canonical).unwrap(), canonical);
+    }
+
+    #[test]
+    fn unknown_word() {
+        assert_eq!(
+            canonicalize("a ninetales").unwrap_err().to_string(),
+            "unknown word: ninetales"
+        );
+    }
+
+    #[test]
+    fn slug_round_trip() {
+        assert_eq!(bytes_to_slug(&[]).unwrap(), "");
+        let bytes = [0, 0, 17, 212, 12, 140, 90, 246];
+        let slug = bytes_to_slug(&bytes).unwrap();
+        assert_eq!(slug, "a-bioengineering-balloted-gobbled");
+        assert!(slug.bytes().all(|b| b.is_ascii_lowercase() || b == b'-'));
+        assert_eq!(slug_to_bytes(&slug).unwrap(), bytes);
+        assert_eq!(slug_to_bytes("A-Bioengineering").unwrap(), &bytes[..4]);
+        assert!(bytes_to_slug(&[1]).is_err());
+        assert!(slug_to_bytes("a-ninetales").is_err());
+    }
+
+    #[test]
+    fn dictation_round_trip() {
+        let bytes = [0, 0, 17, 212, 12, 140, 90, 246];
+        let words = bytes_to_passphrase(&bytes).unwrap();
+        for template in &[
+            DEFAULT_DICTATION_TEMPLATE,
+            "{word}",
+            "{n}/{total} {word},",
+            "{word} ({n})",
+        ] {
+            let dictation = dictate(&words, template).unwrap();
+            assert_eq!(parse_dictation(&dictation, template).unwrap(), bytes);
+        }
+        assert_eq!(dictate(&[], DEFAULT_DICTATION_TEMPLATE).unwrap(), "");
+        assert!(parse_dictation(" ", DEFAULT_DICTATION_TEMPLATE)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            parse_dictation(
+                "Word 1 of 2 : A.\nword 2 of 2: ZYZZYVA .",
+                DEFAULT_DICTATION_TEMPLATE
+            )
+            .unwrap(),
+            [0, 0, 255, 255]
+        );
+    }
+
+    #[test]
+    fn dictation_errors() {
+        let template = DEFAULT_DICTATION_TEMPLATE;
+        // Skipped word.
+        assert_eq!(
+            parse_dictation("word 1 of 3: a. word 3 of 3: a.", template)
+                .unwrap_err()
+                .to_string(),
+            "dictation does not match template at byte 21"
+        );
+        // Missing word at the end.
+        assert!(matches!(
+            parse_dictation("word 1 of 2: a.", template),
+            Err(Error::InvalidDictation { offset: 15 })
+        ));
+        assert!(matches!(
+            parse_dictation("word 1 of 1 a.", template),
+            Err(Error::InvalidDictation { offset: 12 })
+        ));
+        assert!(matches!(
+            parse_dictation("word 1 of 1: ninetales.", template),
+            Err(Error::UnknownWord { .. })
+        ));
+    }
+
+    #[test]
+    fn template_without_word() {
+        assert_eq!(
+            dictate(&["a"], "word {n}").unwrap_err().to_string(),
+            "template must contain {word}"
+        );
+        assert!(matches!(
+            parse_dictation("word 1", "word {n}"),
+            Err(Error::InvalidTemplate { missing: "{word}" })
+        ));
+    }
+
+    #[test]
+    fn templated_output() {
+        let bytes = [0, 0, 17, 212, 255, 255];
+        assert_eq!(
+            format_passphrase(&bytes, "{i}:{word}", ",").unwrap(),
+            "0:a,1:bioengineering,2:zyzzyva"
+        );
+        assert_eq!(
+            format_passphrase(&bytes, "{hex}/{n}/{total}", " ").unwrap(),
+            "0000/1/3 11d4/2/3 ffff/3/3"
+        );
+        assert_eq!(format_passphrase(&bytes, "-", "").unwrap(), "---");
+        assert_eq!(format_passphrase(&[], "{word}", " ").unwrap(), "");
+        assert!(matches!(
+            format_passphrase(&[1], "{word}", " "),
+            Err(Error::InvalidSize { size: 1 })
+        ));
+        // Dictation leaves the extra placeholders alone.
+        assert_eq!(dictate(&["a"], "{i} {word} {hex}").unwrap(), "{i} a {hex}");
+    }
+
+    #[test]
+    fn syllable_hints() {
+        assert_eq!(syllables("zyzzyva"), ["zyz", "zy", "va"]);
+        assert_eq!(syllables("gobbled"), ["gob", "bled"]);
+        assert_eq!(syllables("balloted"), ["bal", "lot", "ed"]);
+        assert_eq!(
+            syllables("bioengineering"),
+            ["bi", "o", "en", "gi", "neer", "ing"]
+        );
+        assert_eq!(syllables("being"), ["be", "ing"]);
+        assert_eq!(syllables("running"), ["run", "ning"]);
+        assert_eq!(syllables("thinking"), ["think", "ing"]);
+        assert_eq!(syllables("kayak"), ["ka", "yak"]);
+        assert_eq!(syllables("mountain"), ["moun", "tain"]);
+        assert_eq!(syllables("mothers"), ["mo", "thers"]);
+        assert_eq!(syllables("pocketknife"), ["pock", "et", "knife"]);
+        assert_eq!(syllables("a"), ["a"]);
+        assert_eq!(syllables("brr"), ["brr"]);
+    }
+
+    #[test]
+    fn screen_reader_round_trip() {
+        let words = bytes_to_passphrase(&[0, 0, 17, 212, 12, 140, 255, 255]).unwrap();
+        let text = screen_reader_text(&words);
+        assert_eq!(
+            text,
+            "word 1 of 4: a. word 2 of 4: bioengineering (bi-o-en-gi-neer-ing). \
+             word 3 of 4: balloted (bal-lot-ed). word 4 of 4: zyzzyva (zyz-zy-va)."
+        );
+        assert_eq!(
+            parse_screen_reader_text(&text).unwrap(),
+            [0, 0, 17, 212, 12, 140, 255, 255]
+        );
+        assert_eq!(
+            parse_screen_reader_text("Word 1 of 1: Zyzzyva (z-y-z-z-y-v-a).").unwrap(),
+            [255, 255]
+        );
+        assert_eq!(screen_reader_text(&[]), "");
+        assert!(matches!(
+            parse_screen_reader_text("word 2 of 2: a (a)."),
+            Err(Error::InvalidDictation { offset: 5 })
+        ));
+    }
+}
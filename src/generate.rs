@@ -0,0 +1,591 @@
+use crate::wordlist::{default_words_by_len, default_words_up_to_len};
+use crate::{EntropySource, Error, MAX_PASSPHRASE_WORDS, WORD_COUNT};
+use std::collections::HashSet;
+
+/// A generated passphrase together with the entropy it carries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    /// The words of the passphrase.
+    pub words: Vec<&'static str>,
+    /// Entropy of the passphrase in bits, reduced by any restrictions on the words chosen.
+    pub entropy_bits: f64,
+}
+
+impl Generated {
+    /// Decode the words into the bytes they encode.
+    ///
+    /// ## Panics
+    ///
+    /// This function panics if a different default wordlist was registered with
+    /// `set_default_wordlist` after the words were generated.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        crate::passphrase_to_bytes(&self.words).expect("generated words are in the wordlist")
+    }
+}
+
+/// A passphrase generated by `generate_passphrase_filtered`, with how many tries it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filtered {
+    /// The words of the passphrase.
+    pub words: Vec<&'static str>,
+    /// Number of passphrases drawn, including the accepted one.
+    pub attempts: usize,
+}
+
+/// Generate a passphrase that contains none of the given words.
+///
+/// Any word drawn from the blocklist is redrawn, so every word is uniform over the rest of the
+/// wordlist and the result reports the entropy that is actually left: `log2(65536 - banned)` bits
+/// per word. Blocklist entries are matched ignoring ASCII case; entries that are not in the
+/// wordlist cannot appear anyway and do not reduce the entropy.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 512, a
+/// NoCandidateWords error if every word is banned, and an RNGError if the RNG failed to generate
+/// bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let generated =
+///     niceware::generate_passphrase_excluding(8, &["zyzzyva"], &mut rand::thread_rng()).unwrap();
+/// assert!(!generated.words.contains(&"zyzzyva"));
+/// assert!(generated.entropy_bits < 128.0);
+/// ```
+pub fn generate_passphrase_excluding<R: EntropySource>(
+    num_words: usize,
+    blocklist: &[&str],
+    rng: &mut R,
+) -> Result<Generated, Error> {
+    check_num_words(num_words)?;
+
+    let banned: HashSet<u16> = blocklist
+        .iter()
+        .filter_map(|word| crate::word_to_index(word).ok())
+        .collect();
+    let allowed = WORD_COUNT - banned.len();
+    if allowed == 0 && num_words > 0 {
+        return Err(Error::NoCandidateWords);
+    }
+
+    let mut chosen = Vec::with_capacity(num_words);
+    while chosen.len() < num_words {
+        let index = random_index(rng)?;
+        if !banned.contains(&index) {
+            chosen.push(crate::index_to_word(index));
+        }
+    }
+
+    Ok(Generated {
+        words: chosen,
+        entropy_bits: uniform_entropy_bits(num_words, allowed),
+    })
+}
+
+/// Generate a passphrase using only words from the given subset of the wordlist.
+///
+/// This suits codes that should only contain, say, animal or food names. The crate does not tag
+/// its wordlist, so the caller supplies the subset. Every word of the result is uniform over the
+/// distinct words of the subset, and the reported entropy is `log2(subset size)` bits per word, so
+/// callers can add words to make up for a small subset.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a subset word is not in the wordlist, since the
+/// result could not be decoded. It also returns a TooManyWords error if `num_words` is greater
+/// than 512, a NoCandidateWords error if the subset is empty, and an RNGError if the RNG failed to
+/// generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let animals = ["aardvark", "badger", "cat", "dog", "emu", "ferret", "gerbil", "horse"];
+/// let generated =
+///     niceware::generate_passphrase_from_subset(4, &animals, &mut rand::thread_rng()).unwrap();
+/// assert!(generated.words.iter().all(|word| animals.contains(word)));
+/// assert_eq!(generated.entropy_bits, 12.0);
+/// ```
+pub fn generate_passphrase_from_subset<R: EntropySource>(
+    num_words: usize,
+    subset: &[&str],
+    rng: &mut R,
+) -> Result<Generated, Error> {
+    check_num_words(num_words)?;
+
+    let mut candidates = subset
+        .iter()
+        .map(|word| crate::word_to_index(word))
+        .collect::<Result<Vec<u16>, Error>>()?;
+    candidates.sort_unstable();
+    candidates.dedup();
+    if candidates.is_empty() && num_words > 0 {
+        return Err(Error::NoCandidateWords);
+    }
+
+    let chosen = (0..num_words)
+        .map(|_| {
+            let index = candidates[random_below(candidates.len(), rng)?];
+            Ok(crate::index_to_word(index))
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok(Generated {
+        words: chosen,
+        entropy_bits: uniform_entropy_bits(num_words, candidates.len()),
+    })
+}
+
+/// Generate passphrases until one passes the caller's `filter`.
+///
+/// Every draw is a fresh uniformly random passphrase, so the accepted one is uniform over the
+/// passphrases the filter accepts. If the filter accepts a fraction `p` of all passphrases, that
+/// costs `-log2(p)` bits of entropy; the crate cannot know `p`, but `attempts` averages `1 / p`
+/// over many calls. The attempt bound keeps a filter that rejects almost everything from looping
+/// forever.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 512, a
+/// FilterExhausted error if no passphrase passed within `max_attempts` draws, and an RNGError if the
+/// RNG failed to generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let no_repeats = |words: &[&str]| {
+///     words.iter().enumerate().all(|(i, word)| !words[..i].contains(word))
+/// };
+/// let filtered =
+///     niceware::generate_passphrase_filtered(4, 100, &mut rand::thread_rng(), no_repeats)
+///         .unwrap();
+/// assert!(no_repeats(&filtered.words));
+/// ```
+pub fn generate_passphrase_filtered<R, F>(
+    num_words: usize,
+    max_attempts: usize,
+    rng: &mut R,
+    mut filter: F,
+) -> Result<Filtered, Error>
+where
+    R: EntropySource,
+    F: FnMut(&[&'static str]) -> bool,
+{
+    check_num_words(num_words)?;
+
+    let mut words = Vec::with_capacity(num_words);
+    for attempt in 1..=max_attempts {
+        words.clear();
+        for _ in 0..num_words {
+            words.push(crate::index_to_word(random_index(rng)?));
+        }
+        if filter(&words) {
+            return Ok(Filtered {
+                words,
+                attempts: attempt,
+            });
+        }
+    }
+    Err(Error::FilterExhausted {
+        attempts: max_attempts,
+    })
+}
+
+/// Generate a passphrase starting with the given words, for memorable identifiers.
+///
+/// Because every word encodes its own two bytes, a random phrase that happens to start with the
+/// prefix is distributed exactly like the prefix followed by random words. So rather than searching
+/// through random candidates, which would take 65536 tries per prefix word, the rest is drawn
+/// directly. The prefix is public, so the reported entropy counts only the drawn words: 16 bits
+/// fewer per prefix word. Prefix words are matched ignoring ASCII case.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a prefix word is not found in the dictionary. It
+/// returns a TooManyWords error if `num_words` is greater than 512 or the prefix is longer than
+/// `num_words`, and an RNGError if the RNG failed to generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let generated =
+///     niceware::generate_vanity_passphrase(&["Zyzzyva"], 4, &mut rand::thread_rng()).unwrap();
+/// assert_eq!(generated.words[0], "zyzzyva");
+/// assert_eq!(generated.entropy_bits, 48.0);
+/// assert_eq!(&generated.to_bytes()[..2], &[255, 255]);
+/// ```
+pub fn generate_vanity_passphrase<R: EntropySource>(
+    prefix: &[&str],
+    num_words: usize,
+    rng: &mut R,
+) -> Result<Generated, Error> {
+    check_num_words(num_words)?;
+    if prefix.len() > num_words {
+        return Err(Error::TooManyWords {
+            num_words: prefix.len(),
+            max_words: num_words,
+        });
+    }
+
+    let mut chosen = Vec::with_capacity(num_words);
+    for word in prefix {
+        chosen.push(crate::index_to_word(crate::word_to_index(word)?));
+    }
+    while chosen.len() < num_words {
+        chosen.push(crate::index_to_word(random_index(rng)?));
+    }
+
+    Ok(Generated {
+        words: chosen,
+        entropy_bits: 16.0 * (num_words - prefix.len()) as f64,
+    })
+}
+
+/// Generate a passphrase whose words joined by `separator` fit in `max_len` bytes.
+///
+/// Many systems cap passwords at 64 characters. For every word count that could fit, words are
+/// limited to the longest length that keeps the worst case within `max_len`, and the count giving
+/// the most entropy is used. Every word is uniform over the words short enough, so the result
+/// reports `log2(short_enough)` bits per word. A 64 byte limit with a one byte separator gives 16
+/// words of at most 3 letters, about 153 bits; many short words beat a few long ones.
+///
+/// ## Errors
+///
+/// This function returns a NoCandidateWords error if not even one word fits in `max_len`, and an
+/// RNGError if the RNG failed to generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let generated =
+///     niceware::generate_passphrase_max_len(64, " ", &mut rand::thread_rng()).unwrap();
+/// assert!(generated.words.join(" ").len() <= 64);
+/// assert!(generated.entropy_bits > 128.0);
+/// ```
+pub fn generate_passphrase_max_len<R: EntropySource>(
+    max_len: usize,
+    separator: &str,
+    rng: &mut R,
+) -> Result<Generated, Error> {
+    // The length order is cached, so calls after the first do not scan the wordlist.
+    let by_len = default_words_by_len();
+    let max_word_len = by_len.last().map_or(0, |word| word.len());
+
+    let best = (1..=MAX_PASSPHRASE_WORDS)
+        .map_while(|num_words| {
+            let budget = max_len.checked_sub(separator.len() * (num_words - 1))? / num_words;
+            let count = default_words_up_to_len(budget.min(max_word_len)).len();
+            (count > 0).then_some((num_words, count))
+        })
+        .max_by(|a, b| {
+            let bits =
+                |&(num_words, count): &(usize, usize)| num_words as f64 * (count as f64).log2();
+            bits(a).total_cmp(&bits(b))
+        });
+    let (num_words, count) = best.ok_or(Error::NoCandidateWords)?;
+
+    let candidates = &by_len[..count];
+    let chosen = (0..num_words)
+        .map(|_| Ok(candidates[random_below(count, rng)?]))
+        .collect::<Result<_, Error>>()?;
+
+    Ok(Generated {
+        words: chosen,
+        entropy_bits: num_words as f64 * (count as f64).log2(),
+    })
+}
+
+/// Salt of the HKDF step of `generate_passphrase_mixed`.
+#[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+const MIX_SALT: &[u8] = b"niceware generate_passphrase_mixed";
+
+/// Generate a passphrase from OS randomness mixed with entropy supplied by the caller.
+///
+/// For users who want to contribute their own randomness, such as dice rolls, keystroke timings
+/// or a hardware token's output, without replacing the CSPRNG. `extra_entropy` is expanded with
+/// HKDF-SHA256 to the length of the passphrase's bytes and XORed into bytes from the OS RNG. The
+/// result is at least as random as the OS bytes whatever `extra_entropy` is, and should the OS RNG
+/// be predictable, as unpredictable as `extra_entropy`, up to 256 bits.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 512, and an RNGError
+/// if the OS RNG failed to generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let dice = b"3 6 1 4 4 2 5 1 6 6 3 2";
+/// let words = niceware::generate_passphrase_mixed(8, dice).unwrap();
+/// assert_eq!(words.len(), 8);
+/// ```
+#[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+pub fn generate_passphrase_mixed(
+    num_words: usize,
+    extra_entropy: &[u8],
+) -> Result<Vec<&'static str>, Error> {
+    mixed_passphrase(num_words, extra_entropy, &mut crate::entropy::system_rng())
+}
+
+#[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+fn mixed_passphrase<R: EntropySource>(
+    num_words: usize,
+    extra_entropy: &[u8],
+    rng: &mut R,
+) -> Result<Vec<&'static str>, Error> {
+    check_num_words(num_words)?;
+
+    let mut bytes = vec![0; num_words * 2];
+    rng.fill_entropy(&mut bytes)?;
+    let mut mask = vec![0; bytes.len()];
+    hkdf::Hkdf::<sha2::Sha256>::new(Some(MIX_SALT), extra_entropy)
+        .expand(&[], &mut mask)
+        .expect("a passphrase is short enough for HKDF output");
+    bytes
+        .iter_mut()
+        .zip(mask)
+        .for_each(|(byte, mask)| *byte ^= mask);
+    crate::bytes_to_passphrase(&bytes)
+}
+
+/// Entropy in bits of `num_words` words drawn uniformly from `candidates` words.
+///
+/// No words carry no entropy, even from no candidates, where `0 * log2(0)` would be NaN.
+fn uniform_entropy_bits(num_words: usize, candidates: usize) -> f64 {
+    if num_words == 0 {
+        return 0.0;
+    }
+    num_words as f64 * (candidates as f64).log2()
+}
+
+pub(crate) fn check_num_words(num_words: usize) -> Result<(), Error> {
+    if num_words > MAX_PASSPHRASE_WORDS {
+        return Err(Error::TooManyWords {
+            num_words,
+            max_words: MAX_PASSPHRASE_WORDS,
+        });
+    }
+    Ok(())
+}
+
+/// Draw a uniformly random word index.
+pub(crate) fn random_index<R: EntropySource>(rng: &mut R) -> Result<u16, Error> {
+    let mut pair = [0; 2];
+    rng.fill_entropy(&mut pair)?;
+    Ok(u16::from_be_bytes(pair))
+}
+
+/// Draw a uniformly random number below `bound`, which must be between 1 and 2^32.
+pub(crate) fn random_below<R: EntropySource>(bound: usize, rng: &mut R) -> Result<usize, Error> {
+    let bound = bound as u64;
+    // Reject draws from the incomplete last copy of 0..bound to avoid modulo bias.
+    let zone = (1 << 32) - (1 << 32) % bound;
+    loop {
+        let mut draw = [0; 4];
+        rng.fill_entropy(&mut draw)?;
+        let draw = u64::from(u32::from_be_bytes(draw));
+        if draw < zone {
+            return Ok((draw % bound) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+    use crate::generate::mixed_passphrase;
+    use crate::generate::random_below;
+    use crate::{
+        generate_passphrase_excluding, generate_passphrase_filtered,
+        generate_passphrase_from_subset, generate_passphrase_max_len, generate_vanity_passphrase,
+        Error,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn excludes_blocklist() {
+        let mut rng = StdRng::seed_from_u64(1);
+        // Ban the first half of the list, including every word starting with 'a'.
+        let blocklist: Vec<&str> = crate::words::ALL_WORDS[..32768].to_vec();
+        let generated = generate_passphrase_excluding(64, &blocklist, &mut rng).unwrap();
+        assert_eq!(generated.words.len(), 64);
+        assert!(generated.words.iter().all(|word| !word.starts_with('a')));
+        assert_eq!(generated.entropy_bits, 64.0 * 15.0);
+    }
+
+    #[test]
+    fn entropy_ignores_unknown_and_duplicate_entries() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let generated =
+            generate_passphrase_excluding(4, &["ninetales", "A", "a"], &mut rng).unwrap();
+        assert!(!generated.words.contains(&"a"));
+        assert_eq!(generated.entropy_bits, 4.0 * 65535f64.log2());
+
+        let generated = generate_passphrase_excluding(4, &[], &mut rng).unwrap();
+        assert_eq!(generated.entropy_bits, 64.0);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert!(matches!(
+            generate_passphrase_excluding(1, crate::words::ALL_WORDS, &mut rng),
+            Err(Error::NoCandidateWords)
+        ));
+        let generated =
+            generate_passphrase_excluding(0, crate::words::ALL_WORDS, &mut rng).unwrap();
+        assert!(generated.words.is_empty());
+        assert_eq!(generated.entropy_bits, 0.0);
+        assert_eq!(
+            generate_passphrase_excluding(513, &[], &mut rng)
+                .unwrap_err()
+                .to_string(),
+            "number of words 513 cannot be greater than 512"
+        );
+    }
+
+    #[test]
+    fn subset_only() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let subset = ["cat", "Dog", "dog", "emu"];
+        let generated = generate_passphrase_from_subset(32, &subset, &mut rng).unwrap();
+        assert!(generated
+            .words
+            .iter()
+            .all(|word| ["cat", "dog", "emu"].contains(word)));
+        assert!(["cat", "dog", "emu"]
+            .iter()
+            .all(|word| generated.words.contains(word)));
+        assert_eq!(generated.entropy_bits, 32.0 * 3f64.log2());
+    }
+
+    #[test]
+    fn invalid_subset() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(
+            generate_passphrase_from_subset(2, &["cat", "ninetales"], &mut rng)
+                .unwrap_err()
+                .to_string(),
+            "unknown word: ninetales"
+        );
+        assert!(matches!(
+            generate_passphrase_from_subset(2, &[], &mut rng),
+            Err(Error::NoCandidateWords)
+        ));
+        let generated = generate_passphrase_from_subset(0, &[], &mut rng).unwrap();
+        assert!(generated.words.is_empty());
+        assert_eq!(generated.entropy_bits, 0.0);
+    }
+
+    #[test]
+    fn random_below_is_in_range() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            seen[random_below(3, &mut rng).unwrap()] = true;
+        }
+        assert_eq!(seen, [true; 3]);
+        assert_eq!(random_below(1, &mut rng).unwrap(), 0);
+    }
+
+    #[test]
+    fn vanity_prefix() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let generated = generate_vanity_passphrase(&["A", "zyzzyva"], 3, &mut rng).unwrap();
+        assert_eq!(&generated.words[..2], &["a", "zyzzyva"]);
+        assert_eq!(generated.entropy_bits, 16.0);
+        assert_eq!(&generated.to_bytes()[..4], &[0, 0, 255, 255]);
+        assert_eq!(generated.to_bytes().len(), 6);
+
+        assert_eq!(
+            generate_vanity_passphrase(&["a"], 1, &mut rng)
+                .unwrap()
+                .entropy_bits,
+            0.0
+        );
+        assert!(matches!(
+            generate_vanity_passphrase(&["a", "a"], 1, &mut rng),
+            Err(Error::TooManyWords {
+                num_words: 2,
+                max_words: 1
+            })
+        ));
+        assert!(matches!(
+            generate_vanity_passphrase(&["ninetales"], 2, &mut rng),
+            Err(Error::UnknownWord { .. })
+        ));
+    }
+
+    #[test]
+    fn filtered_generation() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut calls = 0;
+        let filtered = generate_passphrase_filtered(3, 1000, &mut rng, |words| {
+            calls += 1;
+            words.iter().all(|word| word.len() <= 6)
+        })
+        .unwrap();
+        assert_eq!(filtered.words.len(), 3);
+        assert!(filtered.words.iter().all(|word| word.len() <= 6));
+        assert_eq!(filtered.attempts, calls);
+
+        assert_eq!(
+            generate_passphrase_filtered(2, 5, &mut rng, |_| false)
+                .unwrap_err()
+                .to_string(),
+            "no passphrase passed the filter in 5 attempts"
+        );
+        assert!(matches!(
+            generate_passphrase_filtered(2, 0, &mut rng, |_| true),
+            Err(Error::FilterExhausted { attempts: 0 })
+        ));
+    }
+
+    #[test]
+    fn fits_max_len() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for (max_len, separator) in [(64, " "), (20, ", "), (1, " "), (2000, "")] {
+            let generated = generate_passphrase_max_len(max_len, separator, &mut rng).unwrap();
+            assert!(generated.words.join(separator).len() <= max_len);
+        }
+
+        let generated = generate_passphrase_max_len(64, " ", &mut rng).unwrap();
+        assert_eq!(generated.words.len(), 16);
+        assert!(generated.words.iter().all(|word| word.len() <= 3));
+        let short = crate::words().filter(|word| word.len() <= 3).count();
+        assert_eq!(generated.entropy_bits, 16.0 * (short as f64).log2());
+
+        assert!(matches!(
+            generate_passphrase_max_len(0, " ", &mut rng),
+            Err(Error::NoCandidateWords)
+        ));
+    }
+
+    #[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+    #[test]
+    fn mixes_entropy() {
+        let mixed = mixed_passphrase(8, b"dice", &mut StdRng::seed_from_u64(1)).unwrap();
+        let os_only = crate::generate_passphrase_with(8, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert_eq!(mixed.len(), 8);
+        assert_ne!(mixed, os_only);
+        assert_eq!(
+            mixed,
+            mixed_passphrase(8, b"dice", &mut StdRng::seed_from_u64(1)).unwrap()
+        );
+        assert_ne!(
+            mixed,
+            mixed_passphrase(8, b"dicf", &mut StdRng::seed_from_u64(1)).unwrap()
+        );
+        assert_ne!(
+            mixed,
+            mixed_passphrase(8, b"dice", &mut StdRng::seed_from_u64(2)).unwrap()
+        );
+        assert_eq!(
+            mixed_passphrase(512, b"", &mut StdRng::seed_from_u64(1))
+                .unwrap()
+                .len(),
+            512
+        );
+        assert!(mixed_passphrase(513, b"", &mut StdRng::seed_from_u64(1)).is_err());
+    }
+}
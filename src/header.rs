@@ -0,0 +1,190 @@
+use crate::{pair_to_word, word_to_index, Error};
+use std::convert::TryFrom;
+
+/// Largest payload, in bytes, that a header can describe.
+pub const MAX_PAYLOAD_SIZE: usize = u16::MAX as usize;
+
+/// Escape value of the length byte announcing a second length word.
+const LONG_LENGTH: u8 = u8::MAX;
+
+/// What a headed passphrase contains.
+///
+/// The type is stored as a single tag byte. Tags 0 to 3 are the named variants; `Other` carries
+/// any other tag, and `Other` with a tag of a named variant is read back as that variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PayloadType {
+    /// Arbitrary bytes.
+    Raw,
+    /// Key material.
+    Key,
+    /// A seed that keys are derived from.
+    Seed,
+    /// An identifier rather than a secret.
+    Identifier,
+    /// An application-defined tag.
+    Other(u8),
+}
+
+impl From<u8> for PayloadType {
+    fn from(tag: u8) -> Self {
+        match tag {
+            0 => PayloadType::Raw,
+            1 => PayloadType::Key,
+            2 => PayloadType::Seed,
+            3 => PayloadType::Identifier,
+            tag => PayloadType::Other(tag),
+        }
+    }
+}
+
+impl From<PayloadType> for u8 {
+    fn from(payload_type: PayloadType) -> Self {
+        match payload_type {
+            PayloadType::Raw => 0,
+            PayloadType::Key => 1,
+            PayloadType::Seed => 2,
+            PayloadType::Identifier => 3,
+            PayloadType::Other(tag) => tag,
+        }
+    }
+}
+
+/// Create word-based passphrase from given bytes, prefixed with a header describing them.
+///
+/// The first word holds the type tag and, for payloads shorter than 255 bytes, the length. Longer
+/// payloads get a second word holding the full length. Because the length is explicit, payloads
+/// of any size up to `MAX_PAYLOAD_SIZE` are supported; odd sizes are padded with a zero byte.
+///
+/// ## Errors
+///
+/// This function returns a PayloadTooLarge error if the given slice is longer than
+/// `MAX_PAYLOAD_SIZE`.
+///
+/// ## Examples
+///
+/// ```
+/// use niceware::PayloadType;
+///
+/// let words = niceware::bytes_to_headed_passphrase(PayloadType::Key, &[255, 255, 1]).unwrap();
+/// assert_eq!(words.len(), 3);
+/// assert_eq!(
+///     niceware::headed_passphrase_to_bytes(&words).unwrap(),
+///     (PayloadType::Key, vec![255, 255, 1])
+/// );
+/// ```
+pub fn bytes_to_headed_passphrase(
+    payload_type: PayloadType,
+    bytes: &[u8],
+) -> Result<Vec<&'static str>, Error> {
+    if bytes.len() > MAX_PAYLOAD_SIZE {
+        return Err(Error::PayloadTooLarge {
+            size: bytes.len(),
+            max_size: MAX_PAYLOAD_SIZE,
+        });
+    }
+
+    let mut words = Vec::with_capacity(bytes.len().div_ceil(2) + 2);
+    let tag = u8::from(payload_type);
+    match u8::try_from(bytes.len()) {
+        Ok(len) if len != LONG_LENGTH => words.push(pair_to_word(&[tag, len])),
+        _ => {
+            words.push(pair_to_word(&[tag, LONG_LENGTH]));
+            words.push(pair_to_word(&(bytes.len() as u16).to_be_bytes()));
+        }
+    }
+
+    let pairs = bytes.chunks_exact(2);
+    let last = pairs
+        .remainder()
+        .first()
+        .map(|&byte| pair_to_word(&[byte, 0]));
+    words.extend(pairs.map(pair_to_word).chain(last));
+    Ok(words)
+}
+
+/// Decode words created by `bytes_to_headed_passphrase` into the payload type and bytes.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary. It returns
+/// an InvalidHeader error if the header is missing, the number of words does not match the length
+/// in the header, or the padding byte of an odd-sized payload is not zero.
+pub fn headed_passphrase_to_bytes(words: &[&str]) -> Result<(PayloadType, Vec<u8>), Error> {
+    let (first, rest) = words.split_first().ok_or(Error::InvalidHeader)?;
+    let [tag, len] = word_to_index(first)?.to_be_bytes();
+    let (len, rest) = if len == LONG_LENGTH {
+        let (second, rest) = rest.split_first().ok_or(Error::InvalidHeader)?;
+        (usize::from(word_to_index(second)?), rest)
+    } else {
+        (usize::from(len), rest)
+    };
+
+    if rest.len() != len.div_ceil(2) {
+        return Err(Error::InvalidHeader);
+    }
+    let mut bytes = Vec::with_capacity(rest.len() * 2);
+    for word in rest {
+        bytes.extend(&word_to_index(word)?.to_be_bytes());
+    }
+    if bytes.len() > len && bytes.pop() != Some(0) {
+        return Err(Error::InvalidHeader);
+    }
+    Ok((PayloadType::from(tag), bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytes_to_headed_passphrase, headed_passphrase_to_bytes, Error, PayloadType};
+
+    #[test]
+    fn round_trip() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(600).collect();
+        for &len in &[0, 1, 2, 3, 16, 254, 255, 256, 600] {
+            for &payload_type in &[PayloadType::Raw, PayloadType::Seed, PayloadType::Other(200)] {
+                let words = bytes_to_headed_passphrase(payload_type, &bytes[..len]).unwrap();
+                let header_words = if len < 255 { 1 } else { 2 };
+                assert_eq!(words.len(), header_words + len.div_ceil(2));
+                assert_eq!(
+                    headed_passphrase_to_bytes(&words).unwrap(),
+                    (payload_type, bytes[..len].to_vec())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn header_words() {
+        assert_eq!(
+            bytes_to_headed_passphrase(PayloadType::Raw, &[255, 255]).unwrap(),
+            &["aardvark", "zyzzyva"]
+        );
+        assert_eq!(
+            PayloadType::from(u8::from(PayloadType::Other(1))),
+            PayloadType::Key
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            bytes_to_headed_passphrase(PayloadType::Raw, &vec![0; 65536])
+                .unwrap_err()
+                .to_string(),
+            "payload size 65536 cannot be greater than 65535"
+        );
+        assert!(matches!(
+            headed_passphrase_to_bytes(&[]),
+            Err(Error::InvalidHeader)
+        ));
+        // Header announces 2 bytes, but there are none.
+        assert!(matches!(
+            headed_passphrase_to_bytes(&["aardvark"]),
+            Err(Error::InvalidHeader)
+        ));
+        // Header announces 1 byte, but the padding byte is not zero.
+        assert!(matches!(
+            headed_passphrase_to_bytes(&["aah", "zyzzyva"]),
+            Err(Error::InvalidHeader)
+        ));
+    }
+}
@@ -0,0 +1,355 @@
+use crate::{generate, pair_to_word, word_to_index, EntropySource, Error};
+use std::fmt;
+use std::ops::Range;
+use std::slice::ChunksExact;
+
+/// Create word-based passphrase from given bytes as a lazy iterator.
+///
+/// Nothing is allocated: words are looked up as the iterator is advanced, displayed or, with the
+/// `serde` feature, serialized.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let words = niceware::bytes_to_passphrase_iter(&[0, 0, 255, 255]).unwrap();
+/// assert_eq!(words.clone().collect::<Vec<_>>(), ["a", "zyzzyva"]);
+/// assert_eq!(words.joined("-").to_string(), "a-zyzzyva");
+/// ```
+pub fn bytes_to_passphrase_iter(bytes: &[u8]) -> Result<BytesToPassphraseIter<'_>, Error> {
+    if bytes.len() % 2 != 0 {
+        return Err(Error::InvalidSize { size: bytes.len() });
+    }
+
+    Ok(BytesToPassphraseIter {
+        pairs: bytes.chunks_exact(2),
+        separator: None,
+    })
+}
+
+/// Iterator returned by `bytes_to_passphrase_iter`.
+///
+/// It displays as the remaining words joined by its separator, a space by default. With the
+/// `serde` feature it serializes as a sequence of words, or as one joined string once `joined`
+/// has been called, so phrases can go straight from a byte slice into a JSON response.
+#[derive(Debug, Clone)]
+pub struct BytesToPassphraseIter<'a> {
+    pairs: ChunksExact<'a, u8>,
+    separator: Option<&'a str>,
+}
+
+impl<'a> BytesToPassphraseIter<'a> {
+    /// Display and serialize the words as one string joined by `separator`.
+    pub fn joined(self, separator: &'a str) -> Self {
+        BytesToPassphraseIter {
+            separator: Some(separator),
+            ..self
+        }
+    }
+}
+
+impl Iterator for BytesToPassphraseIter<'_> {
+    type Item = &'static str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pairs.next().map(pair_to_word)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pairs.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for BytesToPassphraseIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.pairs.next_back().map(pair_to_word)
+    }
+}
+
+impl ExactSizeIterator for BytesToPassphraseIter<'_> {}
+
+impl fmt::Display for BytesToPassphraseIter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let separator = self.separator.unwrap_or(" ");
+        for (i, word) in self.clone().enumerate() {
+            if i > 0 {
+                f.write_str(separator)?;
+            }
+            f.write_str(word)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BytesToPassphraseIter<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.separator.is_some() {
+            serializer.collect_str(self)
+        } else {
+            serializer.collect_seq(self.clone())
+        }
+    }
+}
+
+/// Generate a random passphrase as a lazy iterator over its words.
+///
+/// Only the random bytes are stored, two per word, so no vector of words is ever built. Like
+/// `bytes_to_passphrase_iter`, the result can be displayed directly, joined by any separator.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 512, and an RNGError
+/// if the underlying RNG failed to generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let words = niceware::generate_passphrase_iter(4).unwrap();
+/// assert_eq!(words.len(), 4);
+/// assert_eq!(words.joined("-").to_string().matches('-').count(), 3);
+/// ```
+#[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+pub fn generate_passphrase_iter(num_words: usize) -> Result<GeneratedPassphraseIter, Error> {
+    generate_passphrase_iter_with(num_words, &mut crate::entropy::system_rng())
+}
+
+/// Generate a random passphrase as a lazy iterator over its words, using the given entropy
+/// source.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 512, and an RNGError
+/// if the RNG failed to generate bytes.
+pub fn generate_passphrase_iter_with<R: EntropySource>(
+    num_words: usize,
+    rng: &mut R,
+) -> Result<GeneratedPassphraseIter, Error> {
+    generate::check_num_words(num_words)?;
+
+    let mut bytes = vec![0; num_words * 2];
+    rng.fill_entropy(&mut bytes)?;
+    Ok(GeneratedPassphraseIter {
+        bytes,
+        words: 0..num_words,
+        separator: None,
+    })
+}
+
+/// Iterator returned by `generate_passphrase_iter`.
+///
+/// It owns the generated bytes and otherwise behaves like `BytesToPassphraseIter`: it displays as
+/// the remaining words joined by its separator, a space by default.
+#[derive(Debug, Clone)]
+pub struct GeneratedPassphraseIter {
+    bytes: Vec<u8>,
+    words: Range<usize>,
+    separator: Option<&'static str>,
+}
+
+impl GeneratedPassphraseIter {
+    /// Display the words as one string joined by `separator`.
+    pub fn joined(self, separator: &'static str) -> Self {
+        GeneratedPassphraseIter {
+            separator: Some(separator),
+            ..self
+        }
+    }
+
+    /// The bytes encoded by the remaining words.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[self.words.start * 2..self.words.end * 2]
+    }
+
+    fn word(&self, i: usize) -> &'static str {
+        pair_to_word(&self.bytes[i * 2..i * 2 + 2])
+    }
+}
+
+impl Iterator for GeneratedPassphraseIter {
+    type Item = &'static str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.words.next().map(|i| self.word(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.words.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for GeneratedPassphraseIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.words.next_back().map(|i| self.word(i))
+    }
+}
+
+impl ExactSizeIterator for GeneratedPassphraseIter {}
+
+impl fmt::Display for GeneratedPassphraseIter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let separator = self.separator.unwrap_or(" ");
+        for i in self.words.clone() {
+            if i > self.words.start {
+                f.write_str(separator)?;
+            }
+            f.write_str(self.word(i))?;
+        }
+        Ok(())
+    }
+}
+
+/// Decode words into bytes as a lazy iterator yielding two bytes per word.
+///
+/// Nothing is allocated: each word is looked up as the iterator is advanced, so the bytes can go
+/// straight into a fixed buffer, a hasher or a writer.
+///
+/// ## Examples
+///
+/// ```
+/// let mut buf = [0; 4];
+/// for (i, pair) in niceware::passphrase_to_bytes_iter(["a", "zyzzyva"]).enumerate() {
+///     buf[i * 2..i * 2 + 2].copy_from_slice(&pair.unwrap());
+/// }
+/// assert_eq!(buf, [0, 0, 255, 255]);
+/// ```
+pub fn passphrase_to_bytes_iter<I>(words: I) -> PassphraseToBytesIter<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    PassphraseToBytesIter {
+        words: words.into_iter(),
+    }
+}
+
+/// Iterator returned by `passphrase_to_bytes_iter`.
+///
+/// It yields an UnknownWord error for a word that is not found in the dictionary, and carries on
+/// with the next word if advanced again.
+#[derive(Debug, Clone)]
+pub struct PassphraseToBytesIter<I> {
+    words: I,
+}
+
+impl<I> Iterator for PassphraseToBytesIter<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Result<[u8; 2], Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.words
+            .next()
+            .map(|word| word_to_index(word.as_ref()).map(u16::to_be_bytes))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.words.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for PassphraseToBytesIter<I>
+where
+    I: DoubleEndedIterator,
+    I::Item: AsRef<str>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.words
+            .next_back()
+            .map(|word| word_to_index(word.as_ref()).map(u16::to_be_bytes))
+    }
+}
+
+impl<I> ExactSizeIterator for PassphraseToBytesIter<I>
+where
+    I: ExactSizeIterator,
+    I::Item: AsRef<str>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bytes_to_passphrase, bytes_to_passphrase_iter, generate_passphrase_iter_with,
+        passphrase_to_bytes_iter, Error,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn iterates_words() {
+        let bytes = [0, 0, 17, 212, 255, 255];
+        let words = bytes_to_passphrase_iter(&bytes).unwrap();
+        assert_eq!(words.len(), 3);
+        assert_eq!(
+            words.clone().collect::<Vec<_>>(),
+            bytes_to_passphrase(&bytes).unwrap()
+        );
+        assert_eq!(words.clone().next_back(), Some("zyzzyva"));
+        assert_eq!(words.to_string(), "a bioengineering zyzzyva");
+        assert_eq!(bytes_to_passphrase_iter(&[]).unwrap().to_string(), "");
+        assert!(matches!(
+            bytes_to_passphrase_iter(&[1]),
+            Err(Error::InvalidSize { size: 1 })
+        ));
+    }
+
+    #[test]
+    fn generates_lazily() {
+        let mut words = generate_passphrase_iter_with(4, &mut StdRng::seed_from_u64(7)).unwrap();
+        let bytes = words.as_bytes().to_vec();
+        assert_eq!(bytes.len(), 8);
+        let expected = bytes_to_passphrase(&bytes).unwrap();
+        assert_eq!(words.clone().collect::<Vec<_>>(), expected);
+        assert_eq!(words.clone().joined("-").to_string(), expected.join("-"));
+
+        assert_eq!(words.next(), Some(expected[0]));
+        assert_eq!(words.next_back(), Some(expected[3]));
+        assert_eq!(words.len(), 2);
+        assert_eq!(words.as_bytes(), &bytes[2..6]);
+        assert_eq!(words.to_string(), expected[1..3].join(" "));
+
+        assert!(matches!(
+            generate_passphrase_iter_with(513, &mut StdRng::seed_from_u64(7)),
+            Err(Error::TooManyWords { .. })
+        ));
+    }
+
+    #[test]
+    fn iterates_bytes() {
+        let words = ["a", "Bioengineering", "zyzzyva"];
+        let pairs = passphrase_to_bytes_iter(words);
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(
+            pairs.map(Result::unwrap).collect::<Vec<_>>(),
+            [[0, 0], [17, 212], [255, 255]]
+        );
+        assert_eq!(
+            passphrase_to_bytes_iter(words.iter())
+                .next_back()
+                .unwrap()
+                .unwrap(),
+            [255, 255]
+        );
+
+        let mut pairs = passphrase_to_bytes_iter(vec!["ninetales".to_string(), "a".to_string()]);
+        assert!(matches!(pairs.next(), Some(Err(Error::UnknownWord { .. }))));
+        assert_eq!(pairs.next().unwrap().unwrap(), [0, 0]);
+        assert!(pairs.next().is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes() {
+        let words = bytes_to_passphrase_iter(&[0, 0, 255, 255]).unwrap();
+        assert_eq!(serde_json::to_string(&words).unwrap(), r#"["a","zyzzyva"]"#);
+        assert_eq!(
+            serde_json::to_string(&words.joined(" ")).unwrap(),
+            r#""a zyzzyva""#
+        );
+    }
+}
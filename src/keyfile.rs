@@ -0,0 +1,156 @@
+use crate::{bytes_to_passphrase, passphrase_to_bytes, Error};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+/// Prefix of the comment line of minisign and signify key files.
+const COMMENT_PREFIX: &str = "untrusted comment:";
+
+/// Kind of key stored in a minisign or signify key file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyFileKind {
+    /// An Ed25519 public key, laid out the same way by minisign and signify.
+    Public,
+    /// A minisign secret key, usually encrypted with a password.
+    MinisignSecret,
+    /// A signify secret key, usually encrypted with a password.
+    SignifySecret,
+}
+
+impl KeyFileKind {
+    /// Recognize the kind of key from the decoded key bytes.
+    fn of(bytes: &[u8]) -> Option<Self> {
+        match (bytes.len(), bytes.get(..2)) {
+            (42, Some(b"Ed")) => Some(KeyFileKind::Public),
+            (158, Some(b"Ed")) => Some(KeyFileKind::MinisignSecret),
+            (104, Some(b"Ed")) => Some(KeyFileKind::SignifySecret),
+            _ => None,
+        }
+    }
+}
+
+/// Create word-based passphrase from the contents of a minisign or signify key file.
+///
+/// The key line under the `untrusted comment:` line is decoded and its bytes turned into words,
+/// including the algorithm and key id, so `passphrase_to_key_file` can rebuild the file. A public
+/// key is 21 words, and both tools' public keys give the same words for the same key.
+///
+/// ## Errors
+///
+/// This function returns an InvalidKeyFile error if the text does not contain a recognized key.
+///
+/// ## Examples
+///
+/// ```
+/// let file = "untrusted comment: minisign public key E7620F1842B4E81F\n\
+///             RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3\n";
+/// let (kind, words) = niceware::key_file_to_passphrase(file).unwrap();
+/// assert_eq!(kind, niceware::KeyFileKind::Public);
+/// assert_eq!(words.len(), 21);
+/// assert_eq!(
+///     niceware::passphrase_to_key_file(&words, "minisign public key E7620F1842B4E81F").unwrap(),
+///     file
+/// );
+/// ```
+pub fn key_file_to_passphrase(contents: &str) -> Result<(KeyFileKind, Vec<&'static str>), Error> {
+    let mut lines = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    if !lines
+        .next()
+        .is_some_and(|line| line.starts_with(COMMENT_PREFIX))
+    {
+        return Err(Error::InvalidKeyFile);
+    }
+    let bytes = lines
+        .next()
+        .and_then(|line| STANDARD.decode(line).ok())
+        .ok_or(Error::InvalidKeyFile)?;
+    let kind = KeyFileKind::of(&bytes).ok_or(Error::InvalidKeyFile)?;
+    Ok((kind, bytes_to_passphrase(&bytes)?))
+}
+
+/// Rebuild a minisign or signify key file from words created by `key_file_to_passphrase`.
+///
+/// The comment is not part of the words, so it is given again, without the `untrusted comment:`
+/// prefix. The tools do not check it.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary, and an
+/// InvalidKeyFile error if the words do not encode a recognized key.
+pub fn passphrase_to_key_file(words: &[&str], comment: &str) -> Result<String, Error> {
+    let bytes = passphrase_to_bytes(words)?;
+    KeyFileKind::of(&bytes).ok_or(Error::InvalidKeyFile)?;
+    Ok(format!(
+        "{} {}\n{}\n",
+        COMMENT_PREFIX,
+        comment,
+        STANDARD.encode(bytes)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{key_file_to_passphrase, passphrase_to_key_file, Error, KeyFileKind};
+
+    const PUBLIC: &str = "untrusted comment: minisign public key E7620F1842B4E81F\n\
+                          RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3\n";
+
+    #[test]
+    fn public_key() {
+        let (kind, words) = key_file_to_passphrase(PUBLIC).unwrap();
+        assert_eq!(kind, KeyFileKind::Public);
+        assert_eq!(words.len(), 21);
+        let rebuilt =
+            passphrase_to_key_file(&words, "minisign public key E7620F1842B4E81F").unwrap();
+        assert_eq!(rebuilt, PUBLIC);
+
+        // Windows line endings and surrounding blank lines are fine.
+        let crlf = format!("\r\n{}\r\n", PUBLIC.replace('\n', "\r\n"));
+        assert_eq!(key_file_to_passphrase(&crlf).unwrap().1, words);
+    }
+
+    #[test]
+    fn secret_keys() {
+        let mut minisign = vec![0u8; 158];
+        minisign[..6].copy_from_slice(b"EdScB2");
+        let words = crate::bytes_to_passphrase(&minisign).unwrap();
+        let file = passphrase_to_key_file(&words, "minisign encrypted secret key").unwrap();
+        assert_eq!(
+            key_file_to_passphrase(&file).unwrap(),
+            (KeyFileKind::MinisignSecret, words)
+        );
+
+        let mut signify = vec![0u8; 104];
+        signify[..4].copy_from_slice(b"EdBK");
+        let words = crate::bytes_to_passphrase(&signify).unwrap();
+        let file = passphrase_to_key_file(&words, "signify secret key").unwrap();
+        assert_eq!(
+            key_file_to_passphrase(&file).unwrap().0,
+            KeyFileKind::SignifySecret
+        );
+    }
+
+    #[test]
+    fn invalid_files() {
+        for file in [
+            "",
+            "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3",
+            "untrusted comment: no key",
+            "untrusted comment: bad base64\n!!!!",
+            "untrusted comment: too short\nRWQf6LRCGA9i53mlYecO",
+        ] {
+            assert!(matches!(
+                key_file_to_passphrase(file),
+                Err(Error::InvalidKeyFile)
+            ));
+        }
+        assert_eq!(
+            passphrase_to_key_file(&["a"], "comment")
+                .unwrap_err()
+                .to_string(),
+            "not a minisign or signify key file"
+        );
+    }
+}
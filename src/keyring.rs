@@ -0,0 +1,79 @@
+//! Storage of passphrases in the platform keyring, enabled by the `keyring` feature.
+
+use crate::{Error, Passphrase};
+use keyring::Entry;
+
+/// Store a passphrase in the platform's secure storage under `service` and `name`.
+///
+/// The passphrase is stored as its displayed text (its words separated by single spaces),
+/// replacing any passphrase stored under the same service and name. The Keychain is used on macOS,
+/// the Credential Manager on Windows, and on Linux the Secret Service, such as GNOME Keyring or
+/// KWallet, with the kernel keyring as a cache; entries survive a reboot on every platform.
+///
+/// ## Errors
+///
+/// This function returns a Keyring error if the platform storage cannot be reached or refuses the
+/// entry.
+///
+/// ## Examples
+///
+/// ```no_run
+/// let phrase: niceware::Passphrase = rand::random();
+/// niceware::store_in_keyring("my-app", "recovery", &phrase).unwrap();
+/// assert_eq!(niceware::load_from_keyring("my-app", "recovery").unwrap(), phrase);
+/// ```
+pub fn store_in_keyring(service: &str, name: &str, phrase: &Passphrase) -> Result<(), Error> {
+    store_in_entry(&Entry::new(service, name).map_err(keyring_error)?, phrase)
+}
+
+/// Load a passphrase stored by `store_in_keyring`.
+///
+/// ## Errors
+///
+/// This function returns a Keyring error if nothing is stored under `service` and `name` or the
+/// platform storage cannot be reached, and an UnknownWord error if the stored text is not a
+/// passphrase.
+pub fn load_from_keyring(service: &str, name: &str) -> Result<Passphrase, Error> {
+    load_from_entry(&Entry::new(service, name).map_err(keyring_error)?)
+}
+
+fn store_in_entry(entry: &Entry, phrase: &Passphrase) -> Result<(), Error> {
+    entry
+        .set_password(&phrase.to_string())
+        .map_err(keyring_error)
+}
+
+fn load_from_entry(entry: &Entry) -> Result<Passphrase, Error> {
+    entry.get_password().map_err(keyring_error)?.parse()
+}
+
+fn keyring_error(e: keyring::Error) -> Error {
+    Error::Keyring { inner: Box::new(e) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::keyring::{load_from_entry, store_in_entry};
+    use crate::{Error, Passphrase};
+    use keyring::mock::MockCredential;
+    use keyring::Entry;
+
+    #[test]
+    fn mock_round_trip() {
+        // A mock entry of its own, so the process-wide credential builder is left alone.
+        let entry = Entry::new_with_credential(Box::new(MockCredential::default()));
+        let err = load_from_entry(&entry).unwrap_err();
+        assert!(err.to_string().starts_with("keyring access failed: "));
+
+        let phrase = Passphrase::from_words(["A", "Zyzzyva"]).unwrap();
+        store_in_entry(&entry, &phrase).unwrap();
+        assert_eq!(entry.get_password().unwrap(), "a zyzzyva");
+        assert_eq!(load_from_entry(&entry).unwrap(), phrase);
+
+        entry.set_password("a ninetales").unwrap();
+        assert!(matches!(
+            load_from_entry(&entry),
+            Err(Error::UnknownWord { .. })
+        ));
+    }
+}
@@ -16,14 +16,156 @@
 //! ## Examples
 //!
 //! ```
+//! # #[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+//! # {
 //! // Creates 128-bit passphrase which is considered cryptographically secure.
 //! println!("Passphrase: {}", niceware::generate_passphrase(8).unwrap().join(" "));
+//! # }
 //! ```
 
+pub use array::{bytes_to_passphrase_array, passphrase_to_key};
+pub use batch::{passphrases_to_bytes, PassphrasesToBytes};
+pub use builder::PhraseBuilder;
+pub use case::{bytes_to_capitalized_passphrase, capitalized_passphrase_to_bytes};
+pub use checksum::{bytes_to_checked_passphrase, checked_passphrase_to_bytes, CHECK_GROUP_WORDS};
+pub use compare::{distance, phrases_equal, verify_approx, Distance};
+pub use confusable::{
+    generate_passphrase_without_confusables, ConfusableRule, Homophones, WithinOneEdit,
+    DEFAULT_CONFUSABLE_RULES, HOMOPHONES,
+};
+pub use decoder::{DecodeLimits, PhraseDecoder};
+#[cfg(feature = "derive")]
+pub use derive::{
+    challenge_response_phrase, derive_child, next_phrase, salted_passphrase_to_bytes,
+    site_password, SALT_ROUNDS, SITE_PASSWORD_MEMORY_KIB, SITE_PASSWORD_PASSES,
+};
+pub use digest::{
+    collision_probability, digest_reader_to_passphrase, words_needed_for_collision_risk,
+    DIGEST_WORDS,
+};
+pub use entropy::EntropySource;
+#[cfg(feature = "pkcs11")]
+pub use entropy::Pkcs11Rng;
+#[cfg(feature = "rand09")]
+pub use entropy::Rand09;
+#[cfg(feature = "ring-rng")]
+pub use entropy::RingRng;
 pub use error::Error;
+pub use format::{
+    bytes_to_slug, canonicalize, dictate, format_passphrase, parse_dictation,
+    parse_screen_reader_text, screen_reader_text, slug_to_bytes, DEFAULT_DICTATION_TEMPLATE,
+};
+#[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+pub use generate::generate_passphrase_mixed;
+pub use generate::{
+    generate_passphrase_excluding, generate_passphrase_filtered, generate_passphrase_from_subset,
+    generate_passphrase_max_len, generate_vanity_passphrase, Filtered, Generated,
+};
+pub use header::{
+    bytes_to_headed_passphrase, headed_passphrase_to_bytes, PayloadType, MAX_PAYLOAD_SIZE,
+};
+#[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+pub use iter::generate_passphrase_iter;
+pub use iter::{
+    bytes_to_passphrase_iter, generate_passphrase_iter_with, passphrase_to_bytes_iter,
+    BytesToPassphraseIter, GeneratedPassphraseIter, PassphraseToBytesIter,
+};
+#[cfg(feature = "keyfile")]
+pub use keyfile::{key_file_to_passphrase, passphrase_to_key_file, KeyFileKind};
+#[cfg(feature = "keyring")]
+pub use keyring::{load_from_keyring, store_in_keyring};
+pub use lookalike::fold_lookalikes;
+pub use lookup::{lookup_strategy, set_lookup_strategy, LookupStrategy};
+#[cfg(feature = "multibase")]
+pub use multibase::{cid_to_passphrase, multibase_decode};
+#[cfg(feature = "otp")]
+pub use otp::{
+    verify_word_code, word_code_at, word_code_for_counter, MAX_SKEW_STEPS, WORD_CODE_STEP,
+    WORD_CODE_WORDS,
+};
+pub use packing::{bytes_to_passphrase_packed, passphrase_to_bytes_packed, Endianness};
+#[cfg(feature = "otp")]
+pub use pairing::confirmation_code;
+pub use partial::PartialWord;
+pub use passphrase::{xor_phrases, Passphrase, PassphraseRef, PhraseLen};
+#[cfg(feature = "pdf")]
+pub use pdf::{render_backup_pdf, BackupSheetOptions, PaperSize};
+pub use plate::{plate_layout, plate_to_bytes, PlateRow};
+pub use policy::{bytes_to_policy_password, generate_policy_password, Policy};
+pub use profile::{Capitalize, DicewareProfile, MemorableRecipe, WordCase};
+pub use pronounce::{generate_pronounceable_passphrase, pronunciation_difficulty};
+#[cfg(feature = "qr")]
+pub use qr::{phrase_to_qr_png, phrase_to_qr_svg, scanned_text_to_bytes};
+pub use radix::RadixCodec;
+pub use randomart::randomart;
+pub use selftest::self_test;
+pub use shamir::{shamir_combine, shamir_commit, shamir_generate, shamir_split, ShamirCommitment};
+pub use speech::{normalize_speech, speech_to_bytes};
+#[cfg(feature = "ssh")]
+pub use ssh::{ssh_key_from_passphrase, SshKeyPair};
 use std::convert::TryInto;
+pub use word::Word;
+pub use wordlist::{
+    default_wordlist, passphrase_to_bytes_detect, set_default_wordlist,
+    verify_compatibility_with_upstream, wordlist_digest, wordlist_index, words, English, WordList,
+    UPSTREAM_WORDLIST_DIGEST, WORDLIST_INDEX_MAGIC, WORD_COUNT,
+};
 
+pub mod compat;
+
+mod array;
+mod batch;
+mod builder;
+mod case;
+mod checksum;
+mod compare;
+mod confusable;
+mod decoder;
+#[cfg(feature = "derive")]
+mod derive;
+mod digest;
+mod entropy;
 mod error;
+mod format;
+mod generate;
+mod header;
+mod iter;
+#[cfg(feature = "keyfile")]
+mod keyfile;
+#[cfg(feature = "keyring")]
+mod keyring;
+mod lookalike;
+mod lookup;
+#[cfg(any(feature = "derive", feature = "otp"))]
+mod mac;
+#[cfg(feature = "multibase")]
+mod multibase;
+#[cfg(feature = "otp")]
+mod otp;
+mod packing;
+#[cfg(feature = "otp")]
+mod pairing;
+mod partial;
+mod passphrase;
+#[cfg(feature = "pdf")]
+mod pdf;
+#[cfg(feature = "phf")]
+mod phf;
+mod plate;
+mod policy;
+mod profile;
+mod pronounce;
+#[cfg(feature = "qr")]
+mod qr;
+mod radix;
+mod randomart;
+mod selftest;
+mod shamir;
+mod speech;
+#[cfg(feature = "ssh")]
+mod ssh;
+mod word;
+mod wordlist;
 mod words;
 
 const MAX_PASSPHRASE_WORDS: usize = 512;
@@ -41,45 +183,296 @@ pub fn bytes_to_passphrase(bytes: &[u8]) -> Result<Vec<&'static str>, Error> {
         return Err(Error::InvalidSize { size: bytes.len() });
     }
 
-    Ok(bytes
-        .chunks_exact(2)
-        .map(|pair| {
-            let word_index = u16::from_be_bytes(pair.try_into().unwrap());
-            words::ALL_WORDS[usize::from(word_index)]
-        })
-        .collect())
+    Ok(radix::word_digits(bytes).map(index_to_word).collect())
+}
+
+/// Create word-based passphrase from given bytes, appending the words to `words`.
+///
+/// This lets hot loops reuse one vector across calls; clear it first to replace its contents.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes.
+/// `words` is left unchanged in that case.
+pub fn bytes_to_passphrase_extend(
+    bytes: &[u8],
+    words: &mut Vec<&'static str>,
+) -> Result<(), Error> {
+    if bytes.len() % 2 != 0 {
+        return Err(Error::InvalidSize { size: bytes.len() });
+    }
+
+    words.extend(radix::word_digits(bytes).map(index_to_word));
+    Ok(())
+}
+
+/// Create word-based passphrase from given bytes, joined into a single `String`.
+///
+/// This gives the same result as `bytes_to_passphrase(bytes)?.join(separator)`, but computes the
+/// final length up front and copies each word straight into one allocation, without building the
+/// intermediate `Vec` of words.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes.
+pub fn bytes_to_passphrase_string(bytes: &[u8], separator: &str) -> Result<String, Error> {
+    if bytes.len() % 2 != 0 {
+        return Err(Error::InvalidSize { size: bytes.len() });
+    }
+
+    let words = radix::word_digits(bytes).map(index_to_word);
+    let num_words = bytes.len() / 2;
+    let len =
+        words.clone().map(str::len).sum::<usize>() + separator.len() * num_words.saturating_sub(1);
+
+    let mut passphrase = String::with_capacity(len);
+    for (i, word) in words.enumerate() {
+        if i > 0 {
+            passphrase.push_str(separator);
+        }
+        passphrase.push_str(word);
+    }
+    Ok(passphrase)
+}
+
+fn pair_to_word(pair: &[u8]) -> &'static str {
+    index_to_word(u16::from_be_bytes(pair.try_into().unwrap()))
+}
+
+/// Word at `index` in the default wordlist.
+///
+/// This is the mapping behind every encoding in this crate: the word at `index` encodes the two
+/// bytes of `index` as a big-endian `u16`. It lets callers build other chunkings on top of the
+/// wordlist; `words` iterates over the whole list and `WORD_COUNT` is its length.
+///
+/// ## Examples
+///
+/// ```
+/// assert_eq!(niceware::index_to_word(0), "a");
+/// assert_eq!(niceware::index_to_word(0x11d4), "bioengineering");
+/// assert_eq!(niceware::word_to_index("Bioengineering").unwrap(), 0x11d4);
+/// ```
+pub fn index_to_word(index: u16) -> &'static str {
+    match wordlist::custom_default_wordlist() {
+        Some(list) => list.word(index),
+        None => words::ALL_WORDS[usize::from(index)],
+    }
 }
 
 /// Decode words into bytes
 ///
 /// This tries to find words in the dictionary and produce the bytes that would have generated
-/// them.
+/// them. Words can be given as anything iterable over strings: a slice of `&str`, a `Vec<String>`
+/// from deserialized input, or an iterator such as `text.split(',')`.
 ///
 /// ## Errors
 ///
 /// This function returns an UnknownWord error if a word is not found in the dictionary.
-pub fn passphrase_to_bytes(words: &[&str]) -> Result<Vec<u8>, Error> {
-    let mut bytes: Vec<u8> = Vec::with_capacity(words.len() * 2);
+///
+/// ## Examples
+///
+/// ```
+/// let owned = vec!["a".to_string(), "zyzzyva".to_string()];
+/// assert_eq!(niceware::passphrase_to_bytes(&owned).unwrap(), [0, 0, 255, 255]);
+/// assert_eq!(niceware::passphrase_to_bytes("a,zyzzyva".split(',')).unwrap(), [0, 0, 255, 255]);
+/// ```
+pub fn passphrase_to_bytes<I>(words: I) -> Result<Vec<u8>, Error>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut bytes: Vec<u8> = Vec::new();
+    passphrase_to_bytes_extend(words, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Decode words into bytes, appending them to `bytes`.
+///
+/// This lets hot loops reuse one buffer across calls; clear it first to replace its contents.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary. `bytes`
+/// is left unchanged in that case.
+pub fn passphrase_to_bytes_extend<I>(words: I, bytes: &mut Vec<u8>) -> Result<(), Error>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let start = bytes.len();
+    let words = words.into_iter();
+    bytes.reserve(words.size_hint().0 * 2);
 
     for word in words {
-        // If a word is longer than maximum then we will definitely not find it.
-        // MAX_WORD_LEN is tested below.
-        if word.len() > MAX_WORD_LEN {
-            return Err(Error::UnknownWord {
-                word: word.to_string(),
-            });
+        match word_to_index(word.as_ref()) {
+            Ok(word_index) => bytes.extend(&word_index.to_be_bytes()),
+            Err(e) => {
+                bytes.truncate(start);
+                return Err(e);
+            }
         }
-        // All words are ascii (test below) so we can just do ascii lowercase.
-        let word_index = words::ALL_WORDS
-            .binary_search(&&word.to_ascii_lowercase()[..])
-            .map_err(|_| Error::UnknownWord {
-                word: word.to_string(),
-            })?;
-        bytes.extend(&u16::to_be_bytes(word_index.try_into().unwrap()));
+    }
+    Ok(())
+}
+
+/// Decode words into a buffer provided by the caller, without allocating.
+///
+/// This suits stack arrays and locked memory for key material.
+///
+/// ## Errors
+///
+/// This function returns a LengthMismatch error if `bytes` is not exactly two bytes per word, and
+/// an UnknownWord error if a word is not found in the dictionary. `bytes` is zeroed in that case.
+///
+/// ## Examples
+///
+/// ```
+/// let mut key = [0; 4];
+/// niceware::passphrase_to_bytes_into(&["a", "zyzzyva"], &mut key).unwrap();
+/// assert_eq!(key, [0, 0, 255, 255]);
+/// ```
+pub fn passphrase_to_bytes_into<S: AsRef<str>>(words: &[S], bytes: &mut [u8]) -> Result<(), Error> {
+    if bytes.len() != words.len() * 2 {
+        return Err(Error::LengthMismatch {
+            expected: words.len() * 2,
+            actual: bytes.len(),
+        });
+    }
+
+    for (word, pair) in words.iter().zip(bytes.chunks_exact_mut(2)) {
+        match word_to_index(word.as_ref()) {
+            Ok(word_index) => pair.copy_from_slice(&word_index.to_be_bytes()),
+            Err(e) => {
+                bytes.iter_mut().for_each(|byte| *byte = 0);
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decode a whole passphrase string into bytes.
+///
+/// Words may be separated by any run of whitespace, including tabs and newlines, commas and
+/// hyphens, none of which appear in dictionary words. Leading and trailing separators are ignored.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// let bytes = niceware::parse_passphrase("  A,\tbioengineering\n-zyzzyva ").unwrap();
+/// assert_eq!(bytes, [0, 0, 17, 212, 255, 255]);
+/// ```
+pub fn parse_passphrase(text: &str) -> Result<Vec<u8>, Error> {
+    passphrase_to_bytes(split_words(text))
+}
+
+/// Decode words into bytes, reporting every word that fails instead of only the first.
+///
+/// Meant for validators and forms that show all problems at once. Each error comes with the
+/// zero-based position of its word. Like `passphrase_to_bytes`, this takes any iterable of
+/// strings.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord or NonAsciiInput error for every word that is not found in
+/// the dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// let errors = niceware::passphrase_to_bytes_all_errors(&["a", "ninetales", "zyzzyva", "bulbasaur"])
+///     .unwrap_err();
+/// let positions: Vec<usize> = errors.iter().map(|(position, _)| *position).collect();
+/// assert_eq!(positions, [1, 3]);
+/// ```
+pub fn passphrase_to_bytes_all_errors<I>(words: I) -> Result<Vec<u8>, Vec<(usize, Error)>>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let words = words.into_iter();
+    let mut bytes = Vec::with_capacity(words.size_hint().0 * 2);
+    let mut errors = Vec::new();
+    for (position, word) in words.enumerate() {
+        match word_to_index(word.as_ref()) {
+            Ok(word_index) => bytes.extend(&word_index.to_be_bytes()),
+            Err(e) => errors.push((position, e)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(bytes)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Split text into words at whitespace, commas and hyphens.
+fn split_words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| c.is_whitespace() || c == ',' || c == '-')
+        .filter(|word| !word.is_empty())
+}
+
+/// Index of a word in the default wordlist, ignoring ASCII case.
+///
+/// This is the inverse of `index_to_word`.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if the word is not found in the dictionary, or a
+/// NonAsciiInput error if that is because it contains a non-ASCII character.
+pub fn word_to_index(word: &str) -> Result<u16, Error> {
+    match wordlist::custom_default_wordlist() {
+        Some(list) => list.index_of(word).ok_or_else(|| unknown_word(word)),
+        None => english_word_to_index(word),
+    }
+}
+
+/// Index of a word in the English wordlist, ignoring ASCII case.
+fn english_word_to_index(word: &str) -> Result<u16, Error> {
+    // Words that are too long or not ASCII are definitely not in the list (tested below).
+    let mut buf = [0; lookup::LOWERCASE_BUF_LEN];
+    lookup::ascii_lowercase(word, &mut buf)
+        .and_then(lookup::find)
+        .ok_or_else(|| unknown_word(word))
+}
+
+/// Error for a word that is not in the list, naming the first non-ASCII character if it has one.
+/// Words of `bytes` in the English wordlist, whatever the default wordlist.
+fn english_bytes_to_passphrase(bytes: &[u8]) -> Result<Vec<&'static str>, Error> {
+    if bytes.len() % 2 != 0 {
+        return Err(Error::InvalidSize { size: bytes.len() });
+    }
+
+    Ok(radix::word_digits(bytes)
+        .map(|index| words::ALL_WORDS[usize::from(index)])
+        .collect())
+}
+
+/// Bytes of `words` in the English wordlist, whatever the default wordlist.
+fn english_passphrase_to_bytes(words: &[&str]) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(words.len() * 2);
+    for word in words {
+        bytes.extend(&english_word_to_index(word)?.to_be_bytes());
     }
     Ok(bytes)
 }
 
+fn unknown_word(word: &str) -> Error {
+    match word.chars().find(|c| !c.is_ascii()) {
+        Some(character) => Error::NonAsciiInput {
+            word: word.to_string(),
+            character,
+        },
+        None => Error::UnknownWord {
+            word: word.to_string(),
+        },
+    }
+}
+
 /// Convenience funtion to generate a passphrase using OS RNG
 ///
 /// This is a shorthand for generating random bytes, and feeding them to `bytes_to_passphrase`.
@@ -90,29 +483,79 @@ pub fn passphrase_to_bytes(words: &[&str]) -> Result<Vec<u8>, Error> {
 /// ## Errors
 ///
 /// This function returns an RNGError if the underlying RNG failed to generate bytes. It returns an InvalidSize error if the given size is odd.
+///
+/// With the `ring-rng` feature, the bytes come from ring's `SystemRandom` instead of the thread
+/// RNG of `rand`, and a failure is a Ring error.
+#[cfg(any(feature = "os-rng", feature = "ring-rng"))]
 pub fn generate_passphrase(num_words: usize) -> Result<Vec<&'static str>, Error> {
-    use rand::Rng;
-
-    if num_words > MAX_PASSPHRASE_WORDS {
-        return Err(Error::TooManyWords {
-            num_words,
-            max_words: MAX_PASSPHRASE_WORDS,
-        });
-    }
+    generate_passphrase_with(num_words, &mut entropy::system_rng())
+}
 
-    let mut bytes: Vec<u8> = vec![0; usize::from(num_words) * 2];
-    let mut s_rng = rand::thread_rng();
-    s_rng.try_fill(&mut bytes[..])?;
+/// Generate a passphrase using the given entropy source.
+///
+/// This is `generate_passphrase` for callers that bring their own RNG, such as a seeded RNG in
+/// tests or a hardware token.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 512, and an RNGError
+/// if the RNG failed to generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let words = niceware::generate_passphrase_with(8, &mut rand::thread_rng()).unwrap();
+/// assert_eq!(words.len(), 8);
+/// ```
+pub fn generate_passphrase_with<R: EntropySource>(
+    num_words: usize,
+    rng: &mut R,
+) -> Result<Vec<&'static str>, Error> {
+    generate::check_num_words(num_words)?;
 
+    let mut bytes: Vec<u8> = vec![0; num_words * 2];
+    rng.fill_entropy(&mut bytes)?;
     bytes_to_passphrase(&bytes)
 }
 
+/// Generate a passphrase from entropy supplied by the caller, two bytes per word.
+///
+/// This needs no RNG at all, so it works with the default `os-rng` feature disabled, for example
+/// in deterministic WASM runtimes that hand out randomness themselves. The bytes must be
+/// uniformly random for the passphrase to be secure; they are encoded as is.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes, and a
+/// TooManyWords error if it would make more than 512 words.
+///
+/// ## Examples
+///
+/// ```
+/// let words = niceware::generate_passphrase_from_entropy(&[0x11, 0xd4, 255, 255]).unwrap();
+/// assert_eq!(words, ["bioengineering", "zyzzyva"]);
+/// ```
+pub fn generate_passphrase_from_entropy(entropy: &[u8]) -> Result<Vec<&'static str>, Error> {
+    generate::check_num_words(entropy.len() / 2)?;
+    bytes_to_passphrase(entropy)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{bytes_to_passphrase, generate_passphrase, passphrase_to_bytes};
+    #[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+    use crate::generate_passphrase;
+    use crate::{
+        bytes_to_passphrase, bytes_to_passphrase_extend, bytes_to_passphrase_string,
+        generate_passphrase_from_entropy, generate_passphrase_with, parse_passphrase,
+        passphrase_to_bytes, passphrase_to_bytes_all_errors, passphrase_to_bytes_extend,
+        passphrase_to_bytes_into, Error,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::borrow::Cow;
 
     // generate_passphrase
 
+    #[cfg(any(feature = "os-rng", feature = "ring-rng"))]
     #[test]
     fn correct_passphrase_length() {
         assert_eq!(generate_passphrase(1).unwrap().len(), 1);
@@ -121,6 +564,7 @@ mod tests {
         assert_eq!(generate_passphrase(256).unwrap().len(), 256);
     }
 
+    #[cfg(any(feature = "os-rng", feature = "ring-rng"))]
     #[test]
     fn passphrase_oob_num_words_513() {
         assert_eq!(
@@ -129,6 +573,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn passphrase_with_rng() {
+        let words = generate_passphrase_with(4, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert_eq!(words.len(), 4);
+        assert_eq!(
+            words,
+            generate_passphrase_with(4, &mut StdRng::seed_from_u64(1)).unwrap()
+        );
+        assert!(generate_passphrase_with(513, &mut StdRng::seed_from_u64(1)).is_err());
+    }
+
+    #[test]
+    fn passphrase_from_entropy() {
+        assert_eq!(generate_passphrase_from_entropy(&[]).unwrap().len(), 0);
+        assert_eq!(
+            generate_passphrase_from_entropy(&[0, 0, 255, 255]).unwrap(),
+            &["a", "zyzzyva"]
+        );
+        assert_eq!(
+            generate_passphrase_from_entropy(&[0; 1026])
+                .unwrap_err()
+                .to_string(),
+            "number of words 513 cannot be greater than 512"
+        );
+        assert!(generate_passphrase_from_entropy(&[0; 3]).is_err());
+    }
+
     // bytes_to_passphrase
 
     #[test]
@@ -155,6 +626,55 @@ mod tests {
         );
     }
 
+    // bytes_to_passphrase_extend
+
+    #[test]
+    fn extend_passphrase() {
+        let mut words = vec!["zyzzyva"];
+        bytes_to_passphrase_extend(&[0, 0, 17, 212], &mut words).unwrap();
+        assert_eq!(words, &["zyzzyva", "a", "bioengineering"]);
+
+        assert_eq!(
+            bytes_to_passphrase_extend(&[0], &mut words)
+                .unwrap_err()
+                .to_string(),
+            "odd size not supported: 1"
+        );
+        assert_eq!(words.len(), 3);
+    }
+
+    // bytes_to_passphrase_string
+
+    #[test]
+    fn joined_passphrase() {
+        assert_eq!(bytes_to_passphrase_string(&[], " ").unwrap(), "");
+        assert_eq!(
+            bytes_to_passphrase_string(&[255, 255], " ").unwrap(),
+            "zyzzyva"
+        );
+        assert_eq!(
+            bytes_to_passphrase_string(&[0, 0, 17, 212, 12, 140], "-").unwrap(),
+            "a-bioengineering-balloted"
+        );
+        assert_eq!(
+            bytes_to_passphrase_string(&[0, 0, 1], " ")
+                .unwrap_err()
+                .to_string(),
+            "odd size not supported: 3"
+        );
+    }
+
+    #[test]
+    fn joined_passphrase_matches_join() {
+        let bytes = [
+            0, 0, 17, 212, 12, 140, 90, 246, 46, 83, 254, 60, 54, 169, 255, 255,
+        ];
+        assert_eq!(
+            bytes_to_passphrase_string(&bytes, ", ").unwrap(),
+            bytes_to_passphrase(&bytes).unwrap().join(", ")
+        );
+    }
+
     // passphrase_to_bytes
 
     #[test]
@@ -187,6 +707,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn extend_bytes() {
+        let mut bytes = vec![1];
+        passphrase_to_bytes_extend(&["a", "zyzzyva"], &mut bytes).unwrap();
+        assert_eq!(bytes, &[1, 0, 0, 255, 255]);
+
+        assert_eq!(
+            passphrase_to_bytes_extend(&["a", "ninetales"], &mut bytes)
+                .unwrap_err()
+                .to_string(),
+            "unknown word: ninetales"
+        );
+        assert_eq!(bytes, &[1, 0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn decodes_into_buffer() {
+        let mut buf = [7; 4];
+        passphrase_to_bytes_into(&["bioengineering", "zyzzyva"], &mut buf).unwrap();
+        assert_eq!(buf, [17, 212, 255, 255]);
+        assert!(matches!(
+            passphrase_to_bytes_into(&["a", "ninetales"], &mut buf),
+            Err(Error::UnknownWord { .. })
+        ));
+        assert_eq!(buf, [0; 4]);
+        assert!(matches!(
+            passphrase_to_bytes_into(&["a"], &mut buf),
+            Err(Error::LengthMismatch {
+                expected: 2,
+                actual: 4
+            })
+        ));
+        passphrase_to_bytes_into::<&str>(&[], &mut []).unwrap();
+    }
+
+    #[test]
+    fn parses_whole_strings() {
+        assert_eq!(
+            parse_passphrase("a  zyzzyva\r\n").unwrap(),
+            [0, 0, 255, 255]
+        );
+        assert_eq!(
+            parse_passphrase("a, zyzzyva-a").unwrap(),
+            [0, 0, 255, 255, 0, 0]
+        );
+        assert!(parse_passphrase(" \t,").unwrap().is_empty());
+        assert!(matches!(
+            parse_passphrase("a ninetales"),
+            Err(Error::UnknownWord { .. })
+        ));
+    }
+
+    #[test]
+    fn owned_and_iterated_words() {
+        let owned: Vec<String> = vec!["A".into(), "zyzzyva".into()];
+        assert_eq!(passphrase_to_bytes(&owned).unwrap(), &[0, 0, 255, 255]);
+        assert_eq!(passphrase_to_bytes(owned).unwrap(), &[0, 0, 255, 255]);
+        let cows = [Cow::Borrowed("a"), Cow::Owned("zyzzyva".to_string())];
+        assert_eq!(passphrase_to_bytes(&cows).unwrap(), &[0, 0, 255, 255]);
+        assert_eq!(
+            passphrase_to_bytes("a zyzzyva".split_whitespace()).unwrap(),
+            &[0, 0, 255, 255]
+        );
+        assert!(passphrase_to_bytes(Vec::<String>::new())
+            .unwrap()
+            .is_empty());
+        assert!(passphrase_to_bytes(vec!["ninetales".to_string()]).is_err());
+    }
+
+    #[test]
+    fn all_errors() {
+        assert_eq!(
+            passphrase_to_bytes_all_errors(&["a", "Zyzzyva"]).unwrap(),
+            &[0, 0, 255, 255]
+        );
+        assert!(passphrase_to_bytes_all_errors(Vec::<String>::new())
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            passphrase_to_bytes_all_errors("a ninetales".split(' ')).unwrap_err()[0].0,
+            1
+        );
+
+        let errors = passphrase_to_bytes_all_errors(&["ninetales", "a", "zyzzyvа", "bulbasaur"])
+            .unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(&errors[0], (0, Error::UnknownWord { word }) if word == "ninetales"));
+        assert!(matches!(errors[1], (2, Error::NonAsciiInput { .. })));
+        assert!(matches!(&errors[2], (3, Error::UnknownWord { word }) if word == "bulbasaur"));
+    }
+
     #[test]
     fn max_word_len() {
         let max_word_len = crate::words::ALL_WORDS
@@ -0,0 +1,145 @@
+use std::borrow::Cow;
+
+/// Cyrillic and Greek letters that look like ASCII letters, with the letter they look like.
+const LOOKALIKES: &[(char, char)] = &[
+    ('а', 'a'),
+    ('в', 'b'),
+    ('е', 'e'),
+    ('к', 'k'),
+    ('м', 'm'),
+    ('н', 'h'),
+    ('о', 'o'),
+    ('р', 'p'),
+    ('с', 'c'),
+    ('т', 't'),
+    ('у', 'y'),
+    ('х', 'x'),
+    ('ѕ', 's'),
+    ('і', 'i'),
+    ('ј', 'j'),
+    ('һ', 'h'),
+    ('ӏ', 'l'),
+    ('ԁ', 'd'),
+    ('ԛ', 'q'),
+    ('ԝ', 'w'),
+    ('А', 'A'),
+    ('В', 'B'),
+    ('Е', 'E'),
+    ('К', 'K'),
+    ('М', 'M'),
+    ('Н', 'H'),
+    ('О', 'O'),
+    ('Р', 'P'),
+    ('С', 'C'),
+    ('Т', 'T'),
+    ('У', 'Y'),
+    ('Х', 'X'),
+    ('Ѕ', 'S'),
+    ('І', 'I'),
+    ('Ј', 'J'),
+    ('α', 'a'),
+    ('ι', 'i'),
+    ('κ', 'k'),
+    ('ν', 'v'),
+    ('ο', 'o'),
+    ('ρ', 'p'),
+    ('υ', 'u'),
+    ('Α', 'A'),
+    ('Β', 'B'),
+    ('Ε', 'E'),
+    ('Ζ', 'Z'),
+    ('Η', 'H'),
+    ('Ι', 'I'),
+    ('Κ', 'K'),
+    ('Μ', 'M'),
+    ('Ν', 'N'),
+    ('Ο', 'O'),
+    ('Ρ', 'P'),
+    ('Τ', 'T'),
+    ('Υ', 'Y'),
+    ('Χ', 'X'),
+];
+
+/// Replace characters that look like ASCII letters with those letters.
+///
+/// Decoding rejects words with characters outside ASCII with a NonAsciiInput error. Text pasted
+/// from documents or typed on a non-Latin keyboard layout often has lookalikes, such as a Cyrillic
+/// `а` or a fullwidth `ａ`; this maps fullwidth ASCII and the Cyrillic and Greek letters that are
+/// indistinguishable from Latin ones. Other characters are kept, so they are still reported. The
+/// mapping is opt-in because it lets two visibly identical but different strings decode to the
+/// same bytes. It fits `PhraseDecoder::with_normalizer`.
+///
+/// ## Examples
+///
+/// ```
+/// let text = niceware::fold_lookalikes("zyzzyvа ＡＡＨ");
+/// assert_eq!(text, "zyzzyva AAH");
+/// ```
+pub fn fold_lookalikes(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(
+        text.chars()
+            .map(|c| match c {
+                '\u{ff01}'..='\u{ff5e}' => char::from_u32(u32::from(c) - 0xfee0).unwrap(),
+                '\u{3000}' => ' ',
+                _ => LOOKALIKES
+                    .iter()
+                    .find(|(lookalike, _)| *lookalike == c)
+                    .map_or(c, |&(_, ascii)| ascii),
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lookalike::LOOKALIKES;
+    use crate::{fold_lookalikes, passphrase_to_bytes, Error, PhraseDecoder};
+    use std::borrow::Cow;
+
+    #[test]
+    fn reports_non_ascii() {
+        match passphrase_to_bytes(&["a", "zyzzyvа"]) {
+            Err(Error::NonAsciiInput { word, character }) => {
+                assert_eq!(word, "zyzzyvа");
+                assert_eq!(character, '\u{430}');
+            }
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(
+            passphrase_to_bytes(&["ｚyzzyva"]).unwrap_err().to_string(),
+            "non-ASCII character U+FF5A in word: ｚyzzyva"
+        );
+        assert!(matches!(
+            passphrase_to_bytes(&["ninetales"]),
+            Err(Error::UnknownWord { .. })
+        ));
+        assert!(matches!(
+            crate::passphrases_to_bytes(&["ａ"]).next().unwrap(),
+            Err(Error::NonAsciiInput {
+                character: 'ａ',
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn folds_lookalikes() {
+        assert!(matches!(
+            fold_lookalikes("zyzzyva"),
+            Cow::Borrowed("zyzzyva")
+        ));
+        assert_eq!(fold_lookalikes("Ζуzzуvа"), "Zyzzyva");
+        assert_eq!(fold_lookalikes("ａ\u{3000}ＺＹＺＺＹＶＡ"), "a ZYZZYVA");
+        assert_eq!(fold_lookalikes("crenelé"), "crenelé");
+        assert!(LOOKALIKES
+            .iter()
+            .all(|(lookalike, ascii)| !lookalike.is_ascii() && ascii.is_ascii_alphabetic()));
+
+        let mut decoder = PhraseDecoder::new().with_normalizer(fold_lookalikes);
+        decoder.push_text("а zyzzyvа").unwrap();
+        assert_eq!(decoder.finish().unwrap(), [0, 0, 255, 255]);
+    }
+}
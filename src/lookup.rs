@@ -0,0 +1,209 @@
+#[cfg(feature = "phf")]
+use crate::phf;
+use crate::words;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+#[cfg(feature = "phf")]
+include!(concat!(env!("OUT_DIR"), "/phf_table.rs"));
+
+#[cfg(feature = "phf")]
+const DEFAULT_STRATEGY: LookupStrategy = LookupStrategy::PerfectHash;
+#[cfg(not(feature = "phf"))]
+const DEFAULT_STRATEGY: LookupStrategy = LookupStrategy::BinarySearch;
+
+static STRATEGY: AtomicU8 = AtomicU8::new(DEFAULT_STRATEGY as u8);
+static INDEX: OnceLock<HashMap<&'static str, u16>> = OnceLock::new();
+
+/// How decoding finds words in the wordlist.
+///
+/// The strategy is process-wide and can be changed at any time with `set_lookup_strategy`. The
+/// default is chosen per build: `PerfectHash` with the `phf` feature, `BinarySearch` without.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LookupStrategy {
+    /// Binary search in the sorted wordlist. Needs no extra memory.
+    BinarySearch = 0,
+    /// A hash map from word to index. It is built on first use, takes a few megabytes and is kept
+    /// for the life of the process, making repeated decoding faster.
+    HashMap = 1,
+    /// A minimal perfect hash generated at build time by the `phf` feature. Its tables take
+    /// 160 KiB in the binary and nothing at run time, and a lookup hashes the word once and
+    /// compares it with a single candidate.
+    #[cfg(feature = "phf")]
+    PerfectHash = 2,
+}
+
+/// Choose how decoding finds words in the wordlist.
+///
+/// All decoding functions in this crate use the chosen strategy. Results are the same with every
+/// strategy; only speed and memory use differ.
+pub fn set_lookup_strategy(strategy: LookupStrategy) {
+    STRATEGY.store(strategy as u8, Ordering::Relaxed);
+}
+
+/// The strategy decoding currently uses.
+pub fn lookup_strategy() -> LookupStrategy {
+    match STRATEGY.load(Ordering::Relaxed) {
+        0 => LookupStrategy::BinarySearch,
+        1 => LookupStrategy::HashMap,
+        _ => DEFAULT_STRATEGY,
+    }
+}
+
+/// Find the index of a lowercase word.
+pub(crate) fn find(word: &str) -> Option<u16> {
+    Lookup::current().find(word)
+}
+
+/// The current strategy, resolved once so repeated lookups skip the global state.
+#[derive(Clone, Copy)]
+pub(crate) enum Lookup {
+    BinarySearch,
+    HashMap(&'static HashMap<&'static str, u16>),
+    #[cfg(feature = "phf")]
+    PerfectHash,
+}
+
+impl fmt::Debug for Lookup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Lookup::BinarySearch => write!(f, "BinarySearch"),
+            Lookup::HashMap(_) => write!(f, "HashMap"),
+            #[cfg(feature = "phf")]
+            Lookup::PerfectHash => write!(f, "PerfectHash"),
+        }
+    }
+}
+
+impl Lookup {
+    pub(crate) fn current() -> Self {
+        match lookup_strategy() {
+            LookupStrategy::BinarySearch => Lookup::BinarySearch,
+            LookupStrategy::HashMap => Lookup::HashMap(index()),
+            #[cfg(feature = "phf")]
+            LookupStrategy::PerfectHash => Lookup::PerfectHash,
+        }
+    }
+
+    /// Find the index of a lowercase word.
+    pub(crate) fn find(self, word: &str) -> Option<u16> {
+        match self {
+            Lookup::BinarySearch => words::ALL_WORDS
+                .binary_search(&word)
+                .ok()
+                .map(|index| index.try_into().unwrap()),
+            Lookup::HashMap(index) => index.get(word).copied(),
+            #[cfg(feature = "phf")]
+            Lookup::PerfectHash => perfect_hash_find(word),
+        }
+    }
+}
+
+/// Find a lowercase word through the perfect hash: the one word that hashes to its slot.
+#[cfg(feature = "phf")]
+fn perfect_hash_find(word: &str) -> Option<u16> {
+    let (bucket, f1, f2) = phf::hash(PHF_SEED, word.as_bytes());
+    let index = PHF_SLOTS[phf::slot(f1, f2, PHF_DISPLACEMENTS[bucket])];
+    (words::ALL_WORDS[usize::from(index)] == word).then_some(index)
+}
+
+/// Size of the buffer `ascii_lowercase` writes into: `MAX_WORD_LEN` rounded up to a power of two.
+pub(crate) const LOWERCASE_BUF_LEN: usize = 32;
+const _: () = assert!(crate::MAX_WORD_LEN <= LOWERCASE_BUF_LEN);
+
+/// Lowercase a word into `buf` without allocating.
+///
+/// Returns `None` for words that cannot be in the wordlist because they are too long or not ASCII,
+/// so callers can reject them without a lookup.
+pub(crate) fn ascii_lowercase<'b>(
+    word: &str,
+    buf: &'b mut [u8; LOWERCASE_BUF_LEN],
+) -> Option<&'b str> {
+    let bytes = word.as_bytes();
+    if bytes.len() > crate::MAX_WORD_LEN || !bytes.is_ascii() {
+        return None;
+    }
+    buf[..bytes.len()].copy_from_slice(bytes);
+    buf.make_ascii_lowercase();
+    Some(std::str::from_utf8(&buf[..bytes.len()]).expect("ASCII is valid UTF-8"))
+}
+
+fn index() -> &'static HashMap<&'static str, u16> {
+    INDEX.get_or_init(|| {
+        words::ALL_WORDS
+            .iter()
+            .enumerate()
+            .map(|(index, &word)| (word, index.try_into().unwrap()))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lookup::{ascii_lowercase, find, index, LOWERCASE_BUF_LEN};
+    use crate::{lookup_strategy, set_lookup_strategy, words, LookupStrategy};
+
+    #[test]
+    fn strategies_agree() {
+        assert_eq!(index().len(), words::ALL_WORDS.len());
+        for (i, &word) in words::ALL_WORDS.iter().enumerate().step_by(97) {
+            assert_eq!(index().get(word), Some(&(i as u16)));
+            assert_eq!(find(word), Some(i as u16));
+        }
+        assert_eq!(index().get("ninetales"), None);
+    }
+
+    #[cfg(feature = "phf")]
+    #[test]
+    fn perfect_hash() {
+        use crate::lookup::perfect_hash_find;
+
+        for (i, &word) in words::ALL_WORDS.iter().enumerate() {
+            assert_eq!(perfect_hash_find(word), Some(i as u16));
+        }
+        for word in ["", "ninetales", "zyzzyvas", "Zyzzyva"] {
+            assert_eq!(perfect_hash_find(word), None);
+        }
+    }
+
+    #[test]
+    fn lowercases_ascii() {
+        let mut buf = [0; LOWERCASE_BUF_LEN];
+        let all: String = (0..=127u8).map(char::from).collect();
+        for chunk in all.as_bytes().chunks(28) {
+            let chunk = std::str::from_utf8(chunk).unwrap();
+            assert_eq!(
+                ascii_lowercase(chunk, &mut buf),
+                Some(chunk.to_ascii_lowercase().as_str())
+            );
+        }
+        assert_eq!(ascii_lowercase("", &mut buf), Some(""));
+        assert_eq!(ascii_lowercase("ZyZzYvA", &mut buf), Some("zyzzyva"));
+        assert_eq!(ascii_lowercase("zÿzzyva", &mut buf), None);
+        assert_eq!(ascii_lowercase(&"a".repeat(29), &mut buf), None);
+    }
+
+    #[test]
+    fn select_strategy() {
+        // Other tests decode in parallel; every strategy gives them the same results, and the
+        // strategy found here is restored.
+        let previous = lookup_strategy();
+        #[allow(unused_mut)]
+        let mut strategies = vec![LookupStrategy::BinarySearch, LookupStrategy::HashMap];
+        #[cfg(feature = "phf")]
+        strategies.push(LookupStrategy::PerfectHash);
+        for strategy in strategies {
+            set_lookup_strategy(strategy);
+            assert_eq!(lookup_strategy(), strategy);
+            assert_eq!(
+                crate::passphrase_to_bytes(&["Zyzzyva"]).unwrap(),
+                &[255, 255]
+            );
+        }
+        set_lookup_strategy(previous);
+    }
+}
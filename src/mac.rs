@@ -0,0 +1,32 @@
+use crate::{bytes_to_passphrase, Error};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Longest code, in words, that a single HMAC-SHA256 output can fill.
+pub(crate) const MAX_MAC_WORDS: usize = 16;
+
+/// HMAC-SHA256 of the concatenated `parts` under `key`.
+pub(crate) fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// Encode the leading `num_words * 2` bytes of HMAC-SHA256 of `parts` under `key` as words.
+///
+/// Returns a TooManyWords error if `num_words` is greater than `MAX_MAC_WORDS`.
+pub(crate) fn mac_words(
+    key: &[u8],
+    parts: &[&[u8]],
+    num_words: usize,
+) -> Result<Vec<&'static str>, Error> {
+    if num_words > MAX_MAC_WORDS {
+        return Err(Error::TooManyWords {
+            num_words,
+            max_words: MAX_MAC_WORDS,
+        });
+    }
+    bytes_to_passphrase(&hmac_sha256(key, parts)[..num_words * 2])
+}
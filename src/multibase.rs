@@ -0,0 +1,220 @@
+use crate::{bytes_to_passphrase, Error};
+use base64::engine::general_purpose::{STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+use base64::Engine;
+
+/// Alphabet of base58btc, the encoding of CIDv0 and multibase prefix `z`.
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Alphabet of RFC 4648 base32, multibase prefixes `b` and `B`.
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Multihash code of SHA-256, which CIDv0 always uses.
+const SHA2_256: u64 = 0x12;
+
+/// Decode a multibase string into bytes.
+///
+/// The base16 (`f`, `F`), base32 (`b`, `B`), base58btc (`z`), base64 (`m`) and base64url (`u`)
+/// encodings are supported, which covers the CIDs and multihashes in common use.
+///
+/// ## Errors
+///
+/// This function returns an InvalidMultibase error if the prefix is not supported or the rest is
+/// not valid in its encoding.
+///
+/// ## Examples
+///
+/// ```
+/// assert_eq!(niceware::multibase_decode("f01ff").unwrap(), [1, 255]);
+/// ```
+pub fn multibase_decode(text: &str) -> Result<Vec<u8>, Error> {
+    let mut chars = text.chars();
+    let prefix = chars.next().ok_or(Error::InvalidMultibase)?;
+    let data = chars.as_str();
+    let decoded = match prefix {
+        'f' | 'F' => decode_base16(data),
+        'b' | 'B' => decode_base32(data),
+        'z' => decode_base58(data),
+        'm' => STANDARD_NO_PAD.decode(data).ok(),
+        'u' => URL_SAFE_NO_PAD.decode(data).ok(),
+        _ => None,
+    };
+    decoded.ok_or(Error::InvalidMultibase)
+}
+
+/// Create word-based passphrase from the hash digest in a CID.
+///
+/// Both CIDv0 (`Qm...`) and multibase-encoded CIDv1 are accepted; the content type and hash
+/// function are dropped and only the digest is spoken, so the v0 and v1 forms of the same content
+/// give the same words. A SHA-256 digest is 16 words; `num_words` keeps only the leading words,
+/// which is enough to tell identifiers apart by ear but not to rule out a deliberate collision.
+///
+/// ## Errors
+///
+/// This function returns an InvalidMultibase error if the text is not a supported CID, an
+/// InvalidSize error if the digest has an odd number of bytes, and a TooManyWords error if
+/// `num_words` is greater than the number of words in the digest.
+///
+/// ## Examples
+///
+/// ```
+/// let v0 = niceware::cid_to_passphrase("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG", Some(4));
+/// let v1 = niceware::cid_to_passphrase(
+///     "bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34",
+///     Some(4),
+/// );
+/// assert_eq!(v0.unwrap(), v1.unwrap());
+/// ```
+pub fn cid_to_passphrase(cid: &str, num_words: Option<usize>) -> Result<Vec<&'static str>, Error> {
+    let bytes = if cid.len() == 46 && cid.starts_with("Qm") {
+        decode_base58(cid).ok_or(Error::InvalidMultibase)?
+    } else {
+        multibase_decode(cid)?
+    };
+
+    let multihash = if bytes.len() == 34 && bytes[0] == SHA2_256 as u8 && bytes[1] == 32 {
+        &bytes[..]
+    } else {
+        let (version, rest) = read_varint(&bytes).ok_or(Error::InvalidMultibase)?;
+        let (_codec, rest) = read_varint(rest).ok_or(Error::InvalidMultibase)?;
+        if version != 1 {
+            return Err(Error::InvalidMultibase);
+        }
+        rest
+    };
+    let (_hash, rest) = read_varint(multihash).ok_or(Error::InvalidMultibase)?;
+    let (len, digest) = read_varint(rest).ok_or(Error::InvalidMultibase)?;
+    if len != digest.len() as u64 {
+        return Err(Error::InvalidMultibase);
+    }
+
+    let words = bytes_to_passphrase(digest)?;
+    match num_words {
+        Some(num_words) if num_words > words.len() => Err(Error::TooManyWords {
+            num_words,
+            max_words: words.len(),
+        }),
+        Some(num_words) => Ok(words[..num_words].to_vec()),
+        None => Ok(words),
+    }
+}
+
+/// Read an unsigned LEB128 varint, returning it with the rest of the input.
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (i, &byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+    None
+}
+
+fn decode_base16(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return None;
+    }
+    text.as_bytes()
+        .chunks_exact(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+fn decode_base32(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in text.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a == c.to_ascii_lowercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    // Leftover bits are padding and must be zero.
+    (buffer == 0).then_some(bytes)
+}
+
+fn decode_base58(text: &str) -> Option<Vec<u8>> {
+    // Little-endian base-256 digits of the number.
+    let mut digits: Vec<u8> = Vec::with_capacity(text.len());
+    for c in text.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for digit in &mut digits {
+            carry += u32::from(*digit) * 58;
+            *digit = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            digits.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = text.bytes().take_while(|&c| c == b'1').count();
+    Some(
+        std::iter::repeat(0)
+            .take(zeros)
+            .chain(digits.into_iter().rev())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::multibase::{decode_base32, decode_base58};
+    use crate::{cid_to_passphrase, multibase_decode, Error};
+
+    const DIGEST_HEX: &str = "9d6c2be50f706953479ab9df2ce3edca90b68053c00b3004b7f0accbe1e8eedf";
+
+    fn digest() -> Vec<u8> {
+        multibase_decode(&format!("f{}", DIGEST_HEX)).unwrap()
+    }
+
+    #[test]
+    fn decodes_bases() {
+        let cid = multibase_decode(&format!("f01701220{}", DIGEST_HEX)).unwrap();
+        assert_eq!(&cid[4..], digest());
+        for encoded in [
+            "bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34",
+            "BAFYBEIE5NQV6KD3QNFJUPGVZ34WOH3OKSC3IAU6ABMYAJN7QVTF6D2HO34",
+            "zdj7Wg2Qkk4mYgAkVU1kppfQ2sMGz5zPwERVpeWmxCQLDxVoC",
+            "uAXASIJ1sK-UPcGlTR5q53yzj7cqQtoBTwAswBLfwrMvh6O7f",
+        ] {
+            assert_eq!(multibase_decode(encoded).unwrap(), cid, "{}", encoded);
+        }
+        assert_eq!(decode_base58("1112").unwrap(), [0, 0, 0, 1]);
+        assert_eq!(decode_base32("").unwrap(), Vec::<u8>::new());
+        for invalid in ["", "x00", "f0", "fzz", "b1", "z0", "m!"] {
+            assert!(multibase_decode(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn verbalizes_cids() {
+        let words = crate::bytes_to_passphrase(&digest()).unwrap();
+        let v0 = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+        assert_eq!(cid_to_passphrase(v0, None).unwrap(), words);
+        assert_eq!(
+            cid_to_passphrase("zdj7Wg2Qkk4mYgAkVU1kppfQ2sMGz5zPwERVpeWmxCQLDxVoC", Some(3))
+                .unwrap(),
+            &words[..3]
+        );
+        assert!(matches!(
+            cid_to_passphrase(v0, Some(17)),
+            Err(Error::TooManyWords { .. })
+        ));
+        // Version 2 does not exist, and a digest length that does not match is rejected.
+        assert!(matches!(
+            cid_to_passphrase(&format!("f02701220{}", DIGEST_HEX), None),
+            Err(Error::InvalidMultibase)
+        ));
+        assert!(matches!(
+            cid_to_passphrase(&format!("f01701221{}", DIGEST_HEX), None),
+            Err(Error::InvalidMultibase)
+        ));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::compare::constant_time_eq;
+use crate::mac::{hmac_sha256, mac_words, MAX_MAC_WORDS};
+use crate::{word_to_index, Error};
+
+/// Length in seconds of the time step of `word_code_at`.
+pub const WORD_CODE_STEP: u64 = 30;
+
+/// Number of words in a code returned by `word_code_at`.
+pub const WORD_CODE_WORDS: usize = 2;
+
+/// Derive a one-time word code from a shared secret and a counter.
+///
+/// This is HOTP with words: the code is the leading `num_words * 2` bytes of
+/// HMAC-SHA256(`secret`, `counter` as big-endian u64), encoded as a passphrase. Each word carries
+/// 16 bits, so two words are about as hard to guess as a nine-digit code.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 16.
+pub fn word_code_for_counter(
+    secret: &[u8],
+    counter: u64,
+    num_words: usize,
+) -> Result<Vec<&'static str>, Error> {
+    mac_words(secret, &[&counter.to_be_bytes()], num_words)
+}
+
+/// Derive the time-based word code valid at `time`, in seconds since the Unix epoch.
+///
+/// This is TOTP with words: the counter is `time / WORD_CODE_STEP` and the code is
+/// `WORD_CODE_WORDS` words long.
+///
+/// ## Examples
+///
+/// ```
+/// let secret = b"shared secret";
+/// let code = niceware::word_code_at(secret, 1_700_000_000);
+/// assert_eq!(code.len(), 2);
+/// assert!(niceware::verify_word_code(secret, &code, 1_700_000_031, 1).unwrap());
+/// ```
+pub fn word_code_at(secret: &[u8], time: u64) -> Vec<&'static str> {
+    word_code_for_counter(secret, time / WORD_CODE_STEP, WORD_CODE_WORDS).unwrap()
+}
+
+/// Largest `skew_steps` accepted by `verify_word_code`, about five minutes either way.
+pub const MAX_SKEW_STEPS: u64 = 10;
+
+/// Check a word code against the codes valid around `time`.
+///
+/// Codes from up to `skew_steps` time steps before or after `time` are accepted, to allow for
+/// clocks that disagree. Every accepted step computes an HMAC and is another chance for a guess to
+/// match, so `skew_steps` is limited to `MAX_SKEW_STEPS`. The length of `code` decides how many words are compared, so codes from
+/// `word_code_for_counter` with a time-based counter can be verified too. Words are matched
+/// ignoring ASCII case and compared in constant time.
+///
+/// ## Errors
+///
+/// This function returns a TooMuchSkew error if `skew_steps` is greater than `MAX_SKEW_STEPS`, and
+/// an UnknownWord error if a word is not found in the dictionary.
+pub fn verify_word_code(
+    secret: &[u8],
+    code: &[&str],
+    time: u64,
+    skew_steps: u64,
+) -> Result<bool, Error> {
+    if skew_steps > MAX_SKEW_STEPS {
+        return Err(Error::TooMuchSkew {
+            skew_steps,
+            max_skew_steps: MAX_SKEW_STEPS,
+        });
+    }
+    let mut given = Vec::with_capacity(code.len() * 2);
+    for word in code {
+        given.extend(&word_to_index(word)?.to_be_bytes());
+    }
+    if given.is_empty() || code.len() > MAX_MAC_WORDS {
+        return Ok(false);
+    }
+
+    let step = time / WORD_CODE_STEP;
+    let first = step.saturating_sub(skew_steps);
+    let last = step.saturating_add(skew_steps);
+    // Check every step in the window so timing does not reveal which one matched.
+    Ok((first..=last).fold(false, |found, counter| {
+        constant_time_eq(
+            &hmac_sha256(secret, &[&counter.to_be_bytes()])[..given.len()],
+            &given,
+        ) | found
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bytes_to_passphrase, verify_word_code, word_code_at, word_code_for_counter, Error,
+        MAX_SKEW_STEPS, WORD_CODE_STEP,
+    };
+
+    const SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn derives_codes() {
+        // HMAC-SHA256 of counter 1 under the RFC 6238 key starts with 0xec9d.
+        assert_eq!(
+            word_code_for_counter(SECRET, 1, 1).unwrap(),
+            bytes_to_passphrase(&[0xec, 0x9d]).unwrap()
+        );
+        assert_eq!(
+            word_code_at(SECRET, 59),
+            word_code_for_counter(SECRET, 1, 2).unwrap()
+        );
+        assert_eq!(word_code_at(SECRET, 30), word_code_at(SECRET, 59));
+        assert_ne!(word_code_at(SECRET, 59), word_code_at(SECRET, 60));
+        assert_ne!(word_code_at(SECRET, 59), word_code_at(b"other", 59));
+        assert_eq!(
+            word_code_for_counter(SECRET, 1, 17)
+                .unwrap_err()
+                .to_string(),
+            "number of words 17 cannot be greater than 16"
+        );
+    }
+
+    #[test]
+    fn verifies_with_skew() {
+        let time = 1_111_111_111;
+        let code = word_code_at(SECRET, time);
+        assert!(verify_word_code(SECRET, &code, time, 0).unwrap());
+        assert!(verify_word_code(SECRET, &code, time + WORD_CODE_STEP, 1).unwrap());
+        assert!(!verify_word_code(SECRET, &code, time + WORD_CODE_STEP, 0).unwrap());
+        assert!(!verify_word_code(SECRET, &code, time + 3 * WORD_CODE_STEP, 2).unwrap());
+        assert!(!verify_word_code(b"other", &code, time, 1).unwrap());
+        assert!(!verify_word_code(SECRET, &[], time, 1).unwrap());
+
+        let upper: Vec<String> = code.iter().map(|word| word.to_uppercase()).collect();
+        let upper: Vec<&str> = upper.iter().map(String::as_str).collect();
+        assert!(verify_word_code(SECRET, &upper, time, 0).unwrap());
+
+        let long = word_code_for_counter(SECRET, time / WORD_CODE_STEP, 3).unwrap();
+        assert!(verify_word_code(SECRET, &long, time, 0).unwrap());
+        assert!(verify_word_code(SECRET, &["a", "ninetales"], time, 0).is_err());
+
+        assert!(verify_word_code(SECRET, &code, time, MAX_SKEW_STEPS).unwrap());
+        assert!(matches!(
+            verify_word_code(SECRET, &code, time, u64::MAX),
+            Err(Error::TooMuchSkew {
+                skew_steps: u64::MAX,
+                max_skew_steps: MAX_SKEW_STEPS
+            })
+        ));
+    }
+}
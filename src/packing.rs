@@ -0,0 +1,110 @@
+use crate::{index_to_word, word_to_index, Error};
+use std::convert::TryInto;
+
+/// Order of the two bytes each word encodes.
+///
+/// This crate and the original implementation pack a word's index big-endian: the word at index
+/// `hi * 256 + lo` encodes the bytes `[hi, lo]`. Some tools packed their data little-endian, and
+/// their phrases only decode to the right bytes with the same order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// `[hi, lo]`, the order of every other function in this crate.
+    #[default]
+    Big,
+    /// `[lo, hi]`.
+    Little,
+}
+
+impl Endianness {
+    fn index(self, pair: [u8; 2]) -> u16 {
+        match self {
+            Endianness::Big => u16::from_be_bytes(pair),
+            Endianness::Little => u16::from_le_bytes(pair),
+        }
+    }
+
+    fn bytes(self, index: u16) -> [u8; 2] {
+        match self {
+            Endianness::Big => index.to_be_bytes(),
+            Endianness::Little => index.to_le_bytes(),
+        }
+    }
+}
+
+/// Create word-based passphrase from given bytes, reading each pair in the given byte order.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes.
+///
+/// ## Examples
+///
+/// ```
+/// use niceware::Endianness;
+///
+/// let bytes = [212, 17, 0, 0];
+/// let words = niceware::bytes_to_passphrase_packed(&bytes, Endianness::Little).unwrap();
+/// assert_eq!(words, ["bioengineering", "a"]);
+/// assert_eq!(niceware::passphrase_to_bytes_packed(&words, Endianness::Little).unwrap(), bytes);
+/// ```
+pub fn bytes_to_passphrase_packed(
+    bytes: &[u8],
+    endianness: Endianness,
+) -> Result<Vec<&'static str>, Error> {
+    if bytes.len() % 2 != 0 {
+        return Err(Error::InvalidSize { size: bytes.len() });
+    }
+    Ok(bytes
+        .chunks_exact(2)
+        .map(|pair| index_to_word(endianness.index(pair.try_into().unwrap())))
+        .collect())
+}
+
+/// Decode words into bytes, writing each pair in the given byte order.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary.
+pub fn passphrase_to_bytes_packed<I>(words: I, endianness: Endianness) -> Result<Vec<u8>, Error>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let words = words.into_iter();
+    let mut bytes = Vec::with_capacity(words.size_hint().0 * 2);
+    for word in words {
+        bytes.extend(&endianness.bytes(word_to_index(word.as_ref())?));
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bytes_to_passphrase, bytes_to_passphrase_packed, passphrase_to_bytes_packed, Endianness,
+    };
+
+    #[test]
+    fn byte_orders() {
+        let bytes = [0, 0, 17, 212, 12, 140, 255, 255];
+        assert_eq!(
+            bytes_to_passphrase_packed(&bytes, Endianness::default()).unwrap(),
+            bytes_to_passphrase(&bytes).unwrap()
+        );
+
+        let swapped = [0, 0, 212, 17, 140, 12, 255, 255];
+        let words = bytes_to_passphrase_packed(&swapped, Endianness::Little).unwrap();
+        assert_eq!(words, bytes_to_passphrase(&bytes).unwrap());
+        assert_eq!(
+            passphrase_to_bytes_packed(&words, Endianness::Little).unwrap(),
+            swapped
+        );
+        assert_eq!(
+            passphrase_to_bytes_packed(&words, Endianness::Big).unwrap(),
+            bytes
+        );
+
+        assert!(bytes_to_passphrase_packed(&[1], Endianness::Little).is_err());
+        assert!(passphrase_to_bytes_packed(["ninetales"], Endianness::Little).is_err());
+    }
+}
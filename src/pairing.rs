@@ -0,0 +1,54 @@
+use crate::mac::mac_words;
+use crate::Error;
+
+/// Derive a short authentication string for confirming a pairing.
+///
+/// Both devices compute the code from the key they agreed on and a `context` such as a hash of
+/// the handshake transcript, then display it; if the words match on both screens, no one is in the
+/// middle. The code is the leading `words * 2` bytes of HMAC-SHA256(`key`, `context`), so an
+/// attacker has a 1 in 2^(16 * `words`) chance of matching; two words are usually plenty.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `words` is greater than 16.
+///
+/// ## Examples
+///
+/// ```
+/// let alice = niceware::confirmation_code(b"shared key", b"transcript hash", 2).unwrap();
+/// let bob = niceware::confirmation_code(b"shared key", b"transcript hash", 2).unwrap();
+/// assert_eq!(alice, bob);
+/// ```
+pub fn confirmation_code(
+    key: &[u8],
+    context: &[u8],
+    words: usize,
+) -> Result<Vec<&'static str>, Error> {
+    mac_words(key, &[context], words)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytes_to_passphrase, confirmation_code};
+
+    #[test]
+    fn derives_codes() {
+        // RFC 4231 test case 2: HMAC-SHA256("Jefe", "what do ya want for nothing?") = 5bdcc146...
+        assert_eq!(
+            confirmation_code(b"Jefe", b"what do ya want for nothing?", 2).unwrap(),
+            bytes_to_passphrase(&[0x5b, 0xdc, 0xc1, 0x46]).unwrap()
+        );
+        assert_eq!(confirmation_code(b"key", b"context", 16).unwrap().len(), 16);
+        assert!(confirmation_code(b"key", b"context", 0).unwrap().is_empty());
+        assert_ne!(
+            confirmation_code(b"key", b"context", 2).unwrap(),
+            confirmation_code(b"key", b"contexT", 2).unwrap()
+        );
+        assert_eq!(
+            confirmation_code(b"key", b"context", 17)
+                .unwrap_err()
+                .to_string(),
+            "number of words 17 cannot be greater than 16"
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::words::ALL_WORDS;
+use std::ops::Range;
+
+/// A word being typed, with the wordlist entries it can still become.
+///
+/// Characters are pushed one at a time and matched ignoring ASCII case. After every keystroke the
+/// word knows which contiguous range of the wordlist starts with what was typed, so an entry
+/// widget can offer completions, flag a typo as soon as no word matches, and accept the word once
+/// it is complete.
+///
+/// ## Examples
+///
+/// ```
+/// let mut word = niceware::PartialWord::new();
+/// for c in "ZYZ".chars() {
+///     word.push(c);
+/// }
+/// assert!(word.is_unambiguous());
+/// assert_eq!(word.completion(), Some("zyzzyva"));
+/// assert_eq!(word.index(), None);
+///
+/// word.push('q');
+/// assert!(!word.is_valid_prefix());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialWord {
+    typed: String,
+    candidates: Range<usize>,
+}
+
+impl Default for PartialWord {
+    fn default() -> Self {
+        PartialWord {
+            typed: String::new(),
+            candidates: 0..ALL_WORDS.len(),
+        }
+    }
+}
+
+impl PartialWord {
+    /// Create an empty word, for which every word is a candidate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a typed character, narrowing the candidates.
+    ///
+    /// Returns whether any candidate is left.
+    pub fn push(&mut self, c: char) -> bool {
+        self.typed.push(c.to_ascii_lowercase());
+        let candidates = &ALL_WORDS[self.candidates.clone()];
+        let start = candidates.partition_point(|word| *word < self.typed.as_str());
+        let end = start
+            + candidates[start..].partition_point(|word| word.starts_with(self.typed.as_str()));
+        self.candidates = self.candidates.start + start..self.candidates.start + end;
+        self.is_valid_prefix()
+    }
+
+    /// Remove the last typed character, widening the candidates again.
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.typed.pop()?;
+        let typed = std::mem::take(&mut self.typed);
+        *self = Self::default();
+        typed.chars().for_each(|c| {
+            self.push(c);
+        });
+        Some(c)
+    }
+
+    /// Forget everything typed so far.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// What was typed so far, lowercased.
+    pub fn as_str(&self) -> &str {
+        &self.typed
+    }
+
+    /// Range of wordlist indices of the words starting with what was typed.
+    pub fn candidate_range(&self) -> Range<usize> {
+        self.candidates.clone()
+    }
+
+    /// The words starting with what was typed, in wordlist order.
+    pub fn candidates(&self) -> &'static [&'static str] {
+        &ALL_WORDS[self.candidates.clone()]
+    }
+
+    /// Whether at least one word starts with what was typed.
+    pub fn is_valid_prefix(&self) -> bool {
+        !self.candidates.is_empty()
+    }
+
+    /// Whether exactly one word starts with what was typed.
+    pub fn is_unambiguous(&self) -> bool {
+        self.candidates.len() == 1
+    }
+
+    /// Whether what was typed is a word, even if longer words also start with it.
+    pub fn is_complete(&self) -> bool {
+        self.index().is_some()
+    }
+
+    /// The only word that can still be typed, if there is exactly one.
+    pub fn completion(&self) -> Option<&'static str> {
+        if self.is_unambiguous() {
+            Some(ALL_WORDS[self.candidates.start])
+        } else {
+            None
+        }
+    }
+
+    /// Wordlist index of what was typed, if it is a complete word.
+    ///
+    /// Because the list is sorted, an exact match is always the first candidate.
+    pub fn index(&self) -> Option<u16> {
+        self.candidates()
+            .first()
+            .filter(|word| **word == self.typed)
+            .map(|_| self.candidates.start as u16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{words, PartialWord, WORD_COUNT};
+
+    #[test]
+    fn narrows_and_widens() {
+        let mut word = PartialWord::new();
+        assert_eq!(word.candidates().len(), WORD_COUNT);
+        assert!(word.is_valid_prefix());
+        assert!(!word.is_complete());
+
+        // "a" is a word, and many longer words start with it.
+        assert!(word.push('A'));
+        assert_eq!(word.as_str(), "a");
+        assert_eq!(word.index(), Some(0));
+        assert!(!word.is_unambiguous());
+        assert!(word.candidates().iter().all(|w| w.starts_with('a')));
+        let a_words = words().filter(|w| w.starts_with('a')).count();
+        assert_eq!(word.candidate_range(), 0..a_words);
+
+        for c in "ah".chars() {
+            word.push(c);
+        }
+        assert_eq!(word.index(), Some(1));
+
+        assert!(!word.push('!'));
+        assert_eq!(word.completion(), None);
+        assert_eq!(word.index(), None);
+        assert_eq!(word.pop(), Some('!'));
+        assert_eq!(word.index(), Some(1));
+
+        word.clear();
+        assert_eq!(word, PartialWord::new());
+        assert_eq!(word.pop(), None);
+    }
+
+    #[test]
+    fn resolves_last_word() {
+        let mut word = PartialWord::new();
+        for c in "zyzzyva".chars() {
+            word.push(c);
+        }
+        assert!(word.is_unambiguous());
+        assert_eq!(word.index(), Some(u16::MAX));
+        assert_eq!(word.completion(), Some("zyzzyva"));
+        assert!(!word.push('s'));
+        assert!(word.candidate_range().is_empty());
+    }
+}
@@ -0,0 +1,498 @@
+use crate::{
+    bytes_to_passphrase_iter, pair_to_word, word_to_index, BytesToPassphraseIter, Error,
+    MAX_PASSPHRASE_WORDS,
+};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Bound, Index, Range, RangeBounds};
+use std::str::FromStr;
+
+/// A passphrase, stored as the bytes its words encode.
+///
+/// Two bytes per word keeps it compact, and the words are looked up whenever they are needed, so
+/// UI code can render, slice and edit individual words without converting to and from vectors.
+/// It displays as its words separated by spaces; its `Debug` output leaves the words out, so a
+/// passphrase does not end up in logs by accident.
+///
+/// ## Examples
+///
+/// ```
+/// let mut phrase = niceware::Passphrase::from_words(&["a", "bioengineering", "zyzzyva"]).unwrap();
+/// assert_eq!(phrase.len(), 3);
+/// assert_eq!(&phrase[1], "bioengineering");
+/// assert_eq!(phrase.subphrase(1..).to_string(), "bioengineering zyzzyva");
+/// phrase.set_word(0, "Zyzzyva").unwrap();
+/// assert_eq!(phrase.as_bytes(), &[255, 255, 17, 212, 255, 255]);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Passphrase {
+    bytes: Vec<u8>,
+}
+
+impl Passphrase {
+    /// Create a passphrase encoding the given bytes.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an InvalidSize error if the given bytes have an odd length.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        if bytes.len() % 2 != 0 {
+            return Err(Error::InvalidSize { size: bytes.len() });
+        }
+        Ok(Passphrase { bytes })
+    }
+
+    /// Create a passphrase from its words, matched ignoring ASCII case.
+    ///
+    /// Like `passphrase_to_bytes`, this takes any iterable of strings, including `Word`s.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an UnknownWord error if a word is not found in the dictionary.
+    pub fn from_words<I>(words: I) -> Result<Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Ok(Passphrase {
+            bytes: crate::passphrase_to_bytes(words)?,
+        })
+    }
+
+    /// Borrowed view of the whole passphrase.
+    pub fn as_phrase(&self) -> PassphraseRef<'_> {
+        PassphraseRef { bytes: &self.bytes }
+    }
+
+    /// The bytes the words encode.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The bytes the words encode, the counterpart of `words`.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consume the passphrase, returning the bytes its words encode.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Number of words.
+    pub fn len(&self) -> usize {
+        self.as_phrase().len()
+    }
+
+    /// Whether the passphrase has no words.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The word at index `i`, or `None` if out of bounds.
+    pub fn word_at(&self, i: usize) -> Option<&'static str> {
+        self.as_phrase().word_at(i)
+    }
+
+    /// Iterate over the words.
+    pub fn words(&self) -> BytesToPassphraseIter<'_> {
+        self.as_phrase().words()
+    }
+
+    /// Borrowed view of the words in `range`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the range is out of bounds, like slicing does.
+    pub fn subphrase<R: RangeBounds<usize>>(&self, range: R) -> PassphraseRef<'_> {
+        self.as_phrase().subphrase(range)
+    }
+
+    /// Replace the word at index `i`, matched ignoring ASCII case.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an UnknownWord error if the word is not found in the dictionary; the
+    /// passphrase is left unchanged in that case.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn set_word(&mut self, i: usize, word: &str) -> Result<(), Error> {
+        assert!(i < self.len(), "word index {} out of bounds", i);
+        let index = word_to_index(word)?;
+        self.bytes[2 * i..2 * i + 2].copy_from_slice(&index.to_be_bytes());
+        Ok(())
+    }
+}
+
+impl Index<usize> for Passphrase {
+    type Output = str;
+
+    fn index(&self, i: usize) -> &str {
+        self.as_phrase().word(i)
+    }
+}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Passphrase")
+            .field("len", &self.len())
+            .field("words", &"..")
+            .finish()
+    }
+}
+
+impl fmt::Display for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_phrase().fmt(f)
+    }
+}
+
+impl TryFrom<Vec<u8>> for Passphrase {
+    type Error = Error;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Error> {
+        Passphrase::from_bytes(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for Passphrase {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        Passphrase::from_bytes(bytes.to_vec())
+    }
+}
+
+impl From<Passphrase> for Vec<u8> {
+    fn from(phrase: Passphrase) -> Vec<u8> {
+        phrase.bytes
+    }
+}
+
+impl AsRef<[u8]> for Passphrase {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl FromStr for Passphrase {
+    type Err = Error;
+
+    /// Parse a passphrase with `parse_passphrase`, so its displayed form parses back.
+    fn from_str(text: &str) -> Result<Self, Error> {
+        Ok(Passphrase {
+            bytes: crate::parse_passphrase(text)?,
+        })
+    }
+}
+
+/// A borrowed view of some or all of the words of a `Passphrase`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PassphraseRef<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> PassphraseRef<'a> {
+    /// The bytes the words encode.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// The bytes the words encode, the counterpart of `words`.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Number of words.
+    pub fn len(&self) -> usize {
+        self.bytes.len() / 2
+    }
+
+    /// Whether the view has no words.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The word at index `i`, or `None` if out of bounds.
+    pub fn word_at(&self, i: usize) -> Option<&'static str> {
+        self.bytes.get(2 * i..2 * i + 2).map(pair_to_word)
+    }
+
+    /// Iterate over the words.
+    pub fn words(&self) -> BytesToPassphraseIter<'a> {
+        bytes_to_passphrase_iter(self.bytes).expect("a passphrase has an even number of bytes")
+    }
+
+    /// Borrowed view of the words in `range`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the range is out of bounds, like slicing does.
+    pub fn subphrase<R: RangeBounds<usize>>(&self, range: R) -> PassphraseRef<'a> {
+        let Range { start, end } = word_range(range, self.len());
+        PassphraseRef {
+            bytes: &self.bytes[2 * start..2 * end],
+        }
+    }
+
+    fn word(&self, i: usize) -> &'static str {
+        match self.word_at(i) {
+            Some(word) => word,
+            None => panic!("word index {} out of bounds for {} words", i, self.len()),
+        }
+    }
+
+    /// Copy the words into an owned passphrase.
+    pub fn to_passphrase(&self) -> Passphrase {
+        Passphrase {
+            bytes: self.bytes.to_vec(),
+        }
+    }
+}
+
+impl Index<usize> for PassphraseRef<'_> {
+    type Output = str;
+
+    fn index(&self, i: usize) -> &str {
+        self.word(i)
+    }
+}
+
+impl fmt::Debug for PassphraseRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PassphraseRef")
+            .field("len", &self.len())
+            .field("words", &"..")
+            .finish()
+    }
+}
+
+impl fmt::Display for PassphraseRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.words().fmt(f)
+    }
+}
+
+/// Combine two passphrases of equal length word by word with XOR on their bytes.
+///
+/// This is a simple two-way split: for a secret phrase `s` and a random phrase `r` of the same
+/// length, `xor_phrases(s, r)` and `r` can be stored apart, say one in a safe and one memorized,
+/// and XORing them again gives back `s`. Either half alone reveals nothing about the secret as
+/// long as `r` is uniformly random and used only once.
+///
+/// ## Errors
+///
+/// This function returns a LengthMismatch error if the passphrases have different numbers of
+/// words.
+///
+/// ## Examples
+///
+/// ```
+/// use niceware::{xor_phrases, Passphrase};
+///
+/// let secret = Passphrase::from_words(&["bioengineering", "balloted"]).unwrap();
+/// let pad = Passphrase::from_words(&["zyzzyva", "gobbled"]).unwrap();
+/// let safe = xor_phrases(&secret, &pad).unwrap();
+/// assert_eq!(xor_phrases(&safe, &pad).unwrap(), secret);
+/// ```
+pub fn xor_phrases(a: &Passphrase, b: &Passphrase) -> Result<Passphrase, Error> {
+    if a.len() != b.len() {
+        return Err(Error::LengthMismatch {
+            expected: a.len(),
+            actual: b.len(),
+        });
+    }
+    Ok(Passphrase {
+        bytes: a.bytes.iter().zip(&b.bytes).map(|(a, b)| a ^ b).collect(),
+    })
+}
+
+/// Distribution of uniformly random passphrases of a fixed number of words.
+///
+/// Lets rand-based code and property-test generators sample phrases like any other value. The
+/// `Standard` distribution, behind `rng.gen::<Passphrase>()`, samples 8 words.
+///
+/// ## Panics
+///
+/// Sampling panics if the number of words is greater than 512.
+///
+/// ## Examples
+///
+/// ```
+/// use niceware::{Passphrase, PhraseLen};
+/// use rand::Rng;
+///
+/// let mut rng = rand::thread_rng();
+/// assert_eq!(rng.gen::<Passphrase>().len(), 8);
+/// assert_eq!(rng.sample(PhraseLen(12)).len(), 12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PhraseLen(pub usize);
+
+impl Distribution<Passphrase> for PhraseLen {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Passphrase {
+        assert!(
+            self.0 <= MAX_PASSPHRASE_WORDS,
+            "number of words {} cannot be greater than {}",
+            self.0,
+            MAX_PASSPHRASE_WORDS
+        );
+        let mut bytes = vec![0; self.0 * 2];
+        rng.fill_bytes(&mut bytes);
+        Passphrase { bytes }
+    }
+}
+
+impl Distribution<Passphrase> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Passphrase {
+        PhraseLen(8).sample(rng)
+    }
+}
+
+fn word_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end && end <= len,
+        "word range {}..{} out of bounds for {} words",
+        start,
+        end,
+        len
+    );
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{xor_phrases, Error, Passphrase, PhraseLen};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn views_and_edits() {
+        let bytes = vec![0, 0, 17, 212, 12, 140, 255, 255];
+        let mut phrase = Passphrase::from_bytes(bytes.clone()).unwrap();
+        assert_eq!(phrase.len(), 4);
+        assert_eq!(phrase.word_at(3), Some("zyzzyva"));
+        assert_eq!(phrase.word_at(4), None);
+        assert_eq!(&phrase[2], "balloted");
+        assert_eq!(
+            phrase.words().collect::<Vec<_>>(),
+            ["a", "bioengineering", "balloted", "zyzzyva"]
+        );
+        assert_eq!(phrase.to_string(), "a bioengineering balloted zyzzyva");
+        assert_eq!(phrase.to_string().parse::<Passphrase>().unwrap(), phrase);
+        assert_eq!(Passphrase::try_from(&bytes[..]).unwrap(), phrase);
+        assert_eq!(Vec::from(phrase.clone()), bytes);
+        assert!(matches!(
+            Passphrase::try_from(&bytes[..3]),
+            Err(Error::InvalidSize { size: 3 })
+        ));
+
+        let middle = phrase.subphrase(1..=2);
+        assert_eq!(middle.len(), 2);
+        assert_eq!(&middle[0], "bioengineering");
+        assert_eq!(middle.as_bytes(), &bytes[2..6]);
+        assert_eq!(middle.bytes(), &bytes[2..6]);
+        assert_eq!(middle.subphrase(1..).to_string(), "balloted");
+        assert!(phrase.subphrase(4..).is_empty());
+        assert_eq!(middle.to_passphrase().into_bytes(), &bytes[2..6]);
+
+        assert!(matches!(
+            phrase.set_word(1, "ninetales"),
+            Err(Error::UnknownWord { .. })
+        ));
+        assert_eq!(phrase.as_bytes(), &bytes[..]);
+        phrase.set_word(1, "A").unwrap();
+        assert_eq!(phrase.to_string(), "a a balloted zyzzyva");
+
+        assert_eq!(
+            Passphrase::from_words(&["a", "a", "balloted", "zyzzyva"]).unwrap(),
+            phrase
+        );
+        assert!(Passphrase::default().is_empty());
+        assert!(Passphrase::from_bytes(vec![1]).is_err());
+    }
+
+    #[test]
+    fn debug_hides_words() {
+        let phrase = Passphrase::from_words(["a", "bioengineering", "zyzzyva"]).unwrap();
+        assert_eq!(phrase.bytes(), &[0, 0, 17, 212, 255, 255]);
+        assert_eq!(
+            format!("{:?}", phrase),
+            "Passphrase { len: 3, words: \"..\" }"
+        );
+        assert_eq!(
+            format!("{:?}", phrase.subphrase(1..)),
+            "PassphraseRef { len: 2, words: \"..\" }"
+        );
+    }
+
+    #[test]
+    fn xor() {
+        let a = Passphrase::from_bytes(vec![0x0f, 0xf0, 0xaa, 0x55]).unwrap();
+        let b = Passphrase::from_bytes(vec![0xff, 0xff, 0x0f, 0x0f]).unwrap();
+        let c = xor_phrases(&a, &b).unwrap();
+        assert_eq!(c.as_bytes(), &[0xf0, 0x0f, 0xa5, 0x5a]);
+        assert_eq!(xor_phrases(&c, &b).unwrap(), a);
+        assert_eq!(xor_phrases(&b, &c).unwrap(), a);
+        assert!(xor_phrases(&a, &a).unwrap().words().all(|word| word == "a"));
+
+        let short = Passphrase::from_words(&["a"]).unwrap();
+        assert!(matches!(
+            xor_phrases(&a, &short),
+            Err(Error::LengthMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn sampling() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let phrase: Passphrase = rng.gen();
+        assert_eq!(phrase.len(), 8);
+        assert_ne!(phrase, rng.gen());
+        assert_eq!(rng.sample(PhraseLen(0)), Passphrase::default());
+        assert_eq!(rng.sample(PhraseLen(512)).len(), 512);
+
+        let mut a = StdRng::seed_from_u64(2);
+        let mut b = StdRng::seed_from_u64(2);
+        assert_eq!(
+            a.sample(PhraseLen(4)).into_bytes(),
+            crate::passphrase_to_bytes(&crate::generate_passphrase_with(4, &mut b).unwrap())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be greater than 512")]
+    fn sample_too_many_words() {
+        StdRng::seed_from_u64(1).sample(PhraseLen(513));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn subphrase_out_of_bounds() {
+        Passphrase::from_bytes(vec![0, 0]).unwrap().subphrase(..2);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn index_out_of_bounds() {
+        let _ = &Passphrase::from_bytes(vec![0, 0]).unwrap()[1];
+    }
+}
@@ -0,0 +1,286 @@
+//! Printable backup sheets, enabled by the `pdf` feature.
+
+use crate::wordlist::{custom_default_wordlist, default_wordlist_digest};
+use crate::{Error, Passphrase, MAX_WORD_LEN};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+
+/// Columns of the word grid.
+const COLUMNS: usize = 2;
+
+/// Font size of the words, in points.
+const WORD_FONT_SIZE: usize = 11;
+
+/// Width of the longest word with its number in front, in points; Courier is 0.6 em per character.
+const WORD_CELL_WIDTH: usize = (("100. ".len() + MAX_WORD_LEN) * WORD_FONT_SIZE * 6).div_ceil(10);
+
+/// Distance between lines of the word grid, in points.
+const LINE_HEIGHT: usize = 18;
+
+/// Margin between the paper edge and the content, in points.
+const MARGIN: usize = 72;
+
+// Every column must hold the longest word on the narrowest paper, A4.
+const _: () = assert!(COLUMNS * WORD_CELL_WIDTH <= 595 - 2 * MARGIN);
+
+/// Paper size of a backup sheet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PaperSize {
+    /// ISO A4, 210 × 297 mm.
+    #[default]
+    A4,
+    /// US Letter, 8.5 × 11 in.
+    Letter,
+}
+
+impl PaperSize {
+    /// Width and height in PDF points.
+    fn points(self) -> (usize, usize) {
+        match self {
+            PaperSize::A4 => (595, 842),
+            PaperSize::Letter => (612, 792),
+        }
+    }
+
+    fn rows(self) -> usize {
+        let (_, height) = self.points();
+        (height - 2 * MARGIN - 100) / LINE_HEIGHT
+    }
+
+    /// Most words that fit on one sheet of this size.
+    pub fn max_words(self) -> usize {
+        COLUMNS * self.rows()
+    }
+}
+
+/// Options of `render_backup_pdf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupSheetOptions<'a> {
+    /// Paper size of the sheet.
+    pub paper: PaperSize,
+    /// Heading printed at the top; characters outside printable ASCII are shown as `?`.
+    pub title: &'a str,
+}
+
+impl Default for BackupSheetOptions<'_> {
+    fn default() -> Self {
+        BackupSheetOptions {
+            paper: PaperSize::A4,
+            title: "Passphrase backup",
+        }
+    }
+}
+
+/// Render a printable backup sheet of a passphrase as a one-page PDF.
+///
+/// The sheet lists the words numbered in columns, followed by the wordlist version (the name of a
+/// list registered with `set_default_wordlist` or "niceware", and the first bytes of its digest,
+/// taken like `wordlist_digest`) and a checksum (the first bytes of the SHA-256 of the passphrase's
+/// bytes) to confirm a transcription against, all inside cut marks. It only uses the standard
+/// Helvetica and Courier fonts, so every PDF viewer renders it the same way without embedded
+/// fonts.
+///
+/// The columns are wide enough for the longest word of the niceware list. Words of a custom list
+/// that are longer than that can run into the next column.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if the passphrase has more words than
+/// `PaperSize::max_words`.
+///
+/// ## Examples
+///
+/// ```
+/// let phrase = niceware::Passphrase::from_words(["a", "bioengineering"]).unwrap();
+/// let options = niceware::BackupSheetOptions::default();
+/// let pdf = niceware::render_backup_pdf(&phrase, &options).unwrap();
+/// assert!(pdf.starts_with(b"%PDF-"));
+/// ```
+pub fn render_backup_pdf(
+    phrase: &Passphrase,
+    options: &BackupSheetOptions,
+) -> Result<Vec<u8>, Error> {
+    let max_words = options.paper.max_words();
+    if phrase.len() > max_words {
+        return Err(Error::TooManyWords {
+            num_words: phrase.len(),
+            max_words,
+        });
+    }
+
+    let (width, height) = options.paper.points();
+    let mut content = String::new();
+    let mut text = |font: &str, size: usize, x: usize, y: usize, line: &str| {
+        let _ = writeln!(
+            content,
+            "BT /{} {} Tf {} {} Td ({}) Tj ET",
+            font,
+            size,
+            x,
+            y,
+            escape(line)
+        );
+    };
+
+    let top = height - MARGIN;
+    text("F1", 18, MARGIN, top - 18, options.title);
+    let list = custom_default_wordlist().map_or("niceware", |list| list.name());
+    let digest = default_wordlist_digest();
+    let info = format!(
+        "{} words. Wordlist: {} {}. Checksum: {}.",
+        phrase.len(),
+        list,
+        hex(&digest[..4]),
+        hex(&Sha256::digest(phrase.as_bytes())[..4])
+    );
+    text("F1", 10, MARGIN, top - 40, &info);
+
+    let rows = options.paper.rows();
+    let column_width = (width - 2 * MARGIN) / COLUMNS;
+    debug_assert!(column_width >= WORD_CELL_WIDTH);
+    for (i, word) in phrase.words().enumerate() {
+        let x = MARGIN + i / rows * column_width;
+        let y = top - 80 - i % rows * LINE_HEIGHT;
+        let line = format!("{:>3}. {}", i + 1, word);
+        text("F2", WORD_FONT_SIZE, x, y, &line);
+    }
+
+    // L-shaped cut marks at the corners of the content area.
+    let (left, right, bottom) = (MARGIN / 2, width - MARGIN / 2, MARGIN / 2);
+    let top = height - MARGIN / 2;
+    for (x, y, dx, dy) in [
+        (left, top, 1, -1),
+        (right, top, -1, -1),
+        (left, bottom, 1, 1),
+        (right, bottom, -1, 1),
+    ] {
+        let (x, y) = (x as isize, y as isize);
+        let _ = writeln!(
+            content,
+            "{} {} m {} {} l {} {} l S",
+            x + 12 * dx,
+            y,
+            x,
+            y,
+            x,
+            y + 12 * dy
+        );
+    }
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 4 0 R /F2 5 0 R >> >> /Contents 6 0 R >>",
+            width, height
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier >>".to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ),
+    ];
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = write!(pdf, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+    }
+    let xref = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(pdf, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    );
+    Ok(pdf.into_bytes())
+}
+
+/// Escape text for a PDF string literal, replacing anything but printable ASCII with `?`.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pdf::{COLUMNS, WORD_CELL_WIDTH};
+    use crate::{render_backup_pdf, BackupSheetOptions, Error, PaperSize, Passphrase};
+
+    #[test]
+    fn renders_sheet() {
+        let options = BackupSheetOptions {
+            paper: PaperSize::Letter,
+            title: "Vault (home) \u{2013} 2026",
+        };
+        let phrase = Passphrase::from_words(["A", "bioengineering", "zyzzyva"]).unwrap();
+        let pdf = render_backup_pdf(&phrase, &options).unwrap();
+        let pdf = String::from_utf8(pdf).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/MediaBox [0 0 612 792]"));
+        assert!(pdf.contains("(Vault \\(home\\) ? 2026)"));
+        assert!(pdf.contains("(  1. a)"));
+        assert!(pdf.contains("(  3. zyzzyva)"));
+        assert!(pdf.contains("Wordlist: niceware e0ab85e0."));
+
+        // Every cross-reference entry points at the start of its object.
+        let startxref = pdf.rsplit("startxref\n").next().unwrap();
+        let xref: usize = startxref.lines().next().unwrap().parse().unwrap();
+        assert!(pdf[xref..].starts_with("xref\n0 7\n"));
+        for (i, entry) in pdf[xref..].lines().skip(3).take(6).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj\n", i + 1)));
+        }
+    }
+
+    #[test]
+    fn sheet_limits() {
+        let options = BackupSheetOptions::default();
+        let full = Passphrase::from_bytes(vec![0; 2 * PaperSize::A4.max_words()]).unwrap();
+        assert!(render_backup_pdf(&full, &options).is_ok());
+        let too_many = Passphrase::from_bytes(vec![0; 2 * PaperSize::A4.max_words() + 2]).unwrap();
+        assert!(matches!(
+            render_backup_pdf(&too_many, &options),
+            Err(Error::TooManyWords { .. })
+        ));
+    }
+
+    #[test]
+    fn longest_words_fit() {
+        let longest = crate::words::ALL_WORDS
+            .iter()
+            .map(|word| word.len())
+            .max()
+            .unwrap();
+        // Courier advances 0.6 em per character.
+        let needed = (5 + longest) as f64 * 11.0 * 0.6;
+        assert!(needed <= WORD_CELL_WIDTH as f64);
+        for paper in [PaperSize::A4, PaperSize::Letter] {
+            let (width, _) = paper.points();
+            assert!(needed <= ((width - 144) / COLUMNS) as f64);
+        }
+    }
+}
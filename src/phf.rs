@@ -0,0 +1,29 @@
+//! Hash function of the perfect hash lookup.
+//!
+//! The build script includes this file to generate the table for the `phf` feature, so the table
+//! and the lookup always hash words the same way.
+
+/// Number of buckets, one displacement each: four words per bucket on average.
+pub const BUCKETS: usize = 1 << 14;
+
+/// Hash `word` under `seed` into its bucket and the two values that place it in a slot.
+///
+/// The second value is odd, so the slots of a word cycle through all 2^16 slots as the
+/// displacement of its bucket grows.
+pub fn hash(seed: u64, word: &[u8]) -> (usize, u16, u16) {
+    // 64-bit FNV-1a, then a finalizer so that every output bit depends on every byte.
+    let mut hash = 0xcbf2_9ce4_8422_2325 ^ seed;
+    for &byte in word {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    ((hash >> 50) as usize, hash as u16, (hash >> 16) as u16 | 1)
+}
+
+/// Slot of a word with hash values `f1` and `f2` in a bucket with the given displacement.
+pub fn slot(f1: u16, f2: u16, displacement: u16) -> usize {
+    usize::from(f1.wrapping_add(displacement.wrapping_mul(f2)))
+}
@@ -0,0 +1,160 @@
+use crate::{english_passphrase_to_bytes, english_word_to_index, words, Error, PartialWord};
+use std::fmt;
+
+/// One word of a metal backup plate: its number and the letters to stamp or punch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlateRow {
+    /// One-based position of the word in the passphrase.
+    pub number: usize,
+    /// Shortest prefix of the word that identifies it, lowercase.
+    pub letters: &'static str,
+}
+
+impl PlateRow {
+    /// Position of every letter in the alphabet, `a` being 1, for punch grids with a row per
+    /// letter.
+    pub fn coordinates(&self) -> Vec<u8> {
+        self.letters.bytes().map(|b| b - b'a' + 1).collect()
+    }
+}
+
+impl fmt::Display for PlateRow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:>3} {}",
+            self.number,
+            self.letters.to_ascii_uppercase()
+        )?;
+        for coordinate in self.coordinates() {
+            write!(f, " {:>2}", coordinate)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lay out a passphrase for a stamped or punched metal backup plate.
+///
+/// Plates for BIP39 store the first four letters of each word, which identify a word in that
+/// 2048-word list. With 65536 words, four letters identify only about 6,000 niceware words, so
+/// each row holds the shortest prefix that no other word starts with instead: most words need
+/// 5 to 8 letters, and the longest need more. A word that starts a longer word, such as "a", is
+/// stored whole. `plate_to_bytes` reads the prefixes back.
+///
+/// Prefixes come from `PartialWord`, so plates always use the English wordlist, even if another
+/// one was registered with `set_default_wordlist`.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// let rows = niceware::plate_layout(&["zyzzyva"]).unwrap();
+/// assert_eq!(rows[0].letters, "zyz");
+/// assert_eq!(rows[0].to_string(), "  1 ZYZ 26 25 26");
+/// ```
+pub fn plate_layout(words: &[&str]) -> Result<Vec<PlateRow>, Error> {
+    let mut rows = Vec::with_capacity(words.len());
+    for (i, word) in words.iter().enumerate() {
+        let word = words::ALL_WORDS[usize::from(english_word_to_index(word)?)];
+        let mut partial = PartialWord::new();
+        let len = word
+            .chars()
+            .take_while(|&c| {
+                partial.push(c);
+                !partial.is_unambiguous()
+            })
+            .count();
+        rows.push(PlateRow {
+            number: i + 1,
+            letters: &word[..(len + 1).min(word.len())],
+        });
+    }
+    Ok(rows)
+}
+
+/// Decode the letters read off a backup plate made with `plate_layout`.
+///
+/// Each entry is the stamped prefix of a word, matched ignoring ASCII case. A prefix that only one
+/// word starts with stands for that word; otherwise it must be a whole word. Full words are
+/// accepted too, so a plate holding more letters than needed still decodes. Like `plate_layout`,
+/// this always uses the English wordlist.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a prefix matches no word, or several words
+/// without being a word itself.
+///
+/// ## Examples
+///
+/// ```
+/// assert_eq!(niceware::plate_to_bytes(&["A", "ZYZ"]).unwrap(), &[0, 0, 255, 255]);
+/// ```
+pub fn plate_to_bytes(stamped: &[&str]) -> Result<Vec<u8>, Error> {
+    let words = stamped
+        .iter()
+        .map(|letters| {
+            let mut partial = PartialWord::new();
+            letters.chars().for_each(|c| {
+                partial.push(c);
+            });
+            partial
+                .completion()
+                .or_else(|| partial.index().map(|i| words::ALL_WORDS[usize::from(i)]))
+                .ok_or_else(|| Error::UnknownWord {
+                    word: letters.to_string(),
+                })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    english_passphrase_to_bytes(&words)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytes_to_passphrase, plate_layout, plate_to_bytes, words, PlateRow};
+
+    #[test]
+    fn round_trips_every_word() {
+        let all: Vec<&str> = words().collect();
+        let rows = plate_layout(&all).unwrap();
+        let stamped: Vec<&str> = rows.iter().map(|row| row.letters).collect();
+        let bytes = plate_to_bytes(&stamped).unwrap();
+        assert_eq!(bytes_to_passphrase(&bytes).unwrap(), all);
+        assert_eq!(rows.last().unwrap().number, all.len());
+        assert!(rows
+            .iter()
+            .zip(&all)
+            .all(|(row, word)| word.starts_with(row.letters)));
+    }
+
+    #[test]
+    fn rows() {
+        let rows = plate_layout(&["A", "bioengineering"]).unwrap();
+        assert_eq!(
+            rows[0],
+            PlateRow {
+                number: 1,
+                letters: "a"
+            }
+        );
+        assert_eq!(rows[1].letters, "bioeng");
+        assert_eq!(rows[1].coordinates(), [2, 9, 15, 5, 14, 7]);
+        assert_eq!(rows[1].to_string(), "  2 BIOENG  2  9 15  5 14  7");
+
+        assert!(plate_layout(&["ninetales"]).is_err());
+        assert!(plate_to_bytes(&["zz"]).is_err());
+        // "academic" starts other words, so it is stored whole.
+        let rows = plate_layout(&["academic", "aardvark"]).unwrap();
+        assert_eq!(rows[0].letters, "academic");
+        assert_eq!(rows[1].letters, "aardv");
+        assert_eq!(
+            plate_to_bytes(&["ACADEMIC", "aardv"]).unwrap(),
+            crate::passphrase_to_bytes(&["academic", "aardvark"]).unwrap()
+        );
+        // "acad" is neither a word nor a unique prefix.
+        assert!(plate_to_bytes(&["acad"]).is_err());
+        assert_eq!(plate_to_bytes(&["bioengineering"]).unwrap(), [17, 212]);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::generate::{check_num_words, random_below, random_index};
+use crate::wordlist::{default_words_by_len, default_words_up_to_len};
+use crate::{bytes_to_passphrase, index_to_word, word_to_index, EntropySource, Error};
+use sha2::{Digest, Sha256};
+
+/// Symbols a decorated password may end with; all are accepted by common password forms.
+const SYMBOLS: &[u8] = b"!#$%&*+=?@^_~";
+
+/// Requirements a password must meet, such as a site's password rules.
+///
+/// Lengths are counted in characters. The default policy accepts anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Policy {
+    /// Shortest allowed password.
+    pub min_len: usize,
+    /// Longest allowed password, if there is a limit.
+    pub max_len: Option<usize>,
+    /// Whether the password needs an ASCII digit.
+    pub require_digit: bool,
+    /// Whether the password needs an ASCII punctuation character.
+    pub require_symbol: bool,
+    /// Whether the password needs an ASCII uppercase letter.
+    pub require_uppercase: bool,
+}
+
+impl Policy {
+    /// Whether `password` meets every requirement of the policy.
+    pub fn allows(&self, password: &str) -> bool {
+        self.violation(password).is_none()
+    }
+
+    /// The first requirement `password` misses, if any.
+    fn violation(&self, password: &str) -> Option<&'static str> {
+        let len = password.chars().count();
+        if len < self.min_len {
+            Some("min_len")
+        } else if self.max_len.is_some_and(|max_len| len > max_len) {
+            Some("max_len")
+        } else if self.require_digit && !password.bytes().any(|b| b.is_ascii_digit()) {
+            Some("digit")
+        } else if self.require_symbol && !password.bytes().any(|b| b.is_ascii_punctuation()) {
+            Some("symbol")
+        } else if self.require_uppercase && !password.bytes().any(|b| b.is_ascii_uppercase()) {
+            Some("uppercase")
+        } else {
+            None
+        }
+    }
+}
+
+/// Create a password from given bytes that meets `policy`, decorating the passphrase as needed.
+///
+/// The words are joined with `separator`. If the policy needs an uppercase letter, the first word
+/// is capitalized; if it needs a digit or a symbol, one is appended. The decorations are derived
+/// from a hash of the bytes, so the same bytes always give the same password and no entropy is
+/// spent on them. Decorations the phrase already has, such as a symbol used as the separator, are
+/// not added again. Removing the trailing decorations leaves an ordinary passphrase that decodes
+/// back to the bytes.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes, and a
+/// PolicyViolation error naming the requirement the decorated password still misses, such as
+/// `min_len` if it is too short.
+///
+/// ## Examples
+///
+/// ```
+/// let policy = niceware::Policy {
+///     require_digit: true,
+///     require_uppercase: true,
+///     ..Default::default()
+/// };
+/// let password = niceware::bytes_to_policy_password(&[0, 0, 255, 255], &policy, " ").unwrap();
+/// assert!(password.starts_with("A zyzzyva"));
+/// assert!(policy.allows(&password));
+/// ```
+pub fn bytes_to_policy_password(
+    bytes: &[u8],
+    policy: &Policy,
+    separator: &str,
+) -> Result<String, Error> {
+    let mut password = bytes_to_passphrase(bytes)?.join(separator);
+
+    let hash = Sha256::new()
+        .chain_update(b"niceware policy")
+        .chain_update(bytes)
+        .finalize();
+    if policy.require_uppercase && !password.bytes().any(|b| b.is_ascii_uppercase()) {
+        if let Some(first) = password.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+    }
+    if policy.require_digit && !password.bytes().any(|b| b.is_ascii_digit()) {
+        password.push(char::from(b'0' + hash[0] % 10));
+    }
+    if policy.require_symbol && !password.bytes().any(|b| b.is_ascii_punctuation()) {
+        password.push(char::from(SYMBOLS[usize::from(hash[1]) % SYMBOLS.len()]));
+    }
+
+    match policy.violation(&password) {
+        None => Ok(password),
+        Some(requirement) => Err(Error::PolicyViolation {
+            len: password.chars().count(),
+            requirement,
+        }),
+    }
+}
+
+/// Generate a password of at least `num_words` random words that meets `policy`.
+///
+/// Words are added one at a time beyond `num_words` until the password is long enough for the
+/// policy; each added word only adds entropy. See `bytes_to_policy_password` for the decorations.
+/// With a maximum length, every word is drawn uniformly from the words that keep the window
+/// reachable: short enough to leave room for the words still needed, and either long enough to
+/// finish the password or short enough to leave room for one more word. Such words carry a little
+/// less than 16 bits each, but a window that `num_words` words can meet is never missed by chance.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if more than 512 words would be needed, a
+/// PolicyViolation error if no `num_words` words fit within the maximum length, and an RNGError if
+/// the RNG failed to generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let policy = niceware::Policy {
+///     min_len: 20,
+///     max_len: Some(64),
+///     require_digit: true,
+///     require_symbol: true,
+///     require_uppercase: true,
+/// };
+/// let password =
+///     niceware::generate_policy_password(4, &policy, "-", &mut rand::thread_rng()).unwrap();
+/// assert!(policy.allows(&password));
+/// ```
+pub fn generate_policy_password<R: EntropySource>(
+    num_words: usize,
+    policy: &Policy,
+    separator: &str,
+    rng: &mut R,
+) -> Result<String, Error> {
+    check_num_words(num_words)?;
+
+    let separator_len = separator.chars().count();
+    let shortest = default_words_by_len().first().map_or(0, |word| word.len());
+    // Start with the digit and symbol that `bytes_to_policy_password` appends.
+    let mut len =
+        usize::from(policy.require_digit && !separator.bytes().any(|b| b.is_ascii_digit()))
+            + usize::from(
+                policy.require_symbol && !separator.bytes().any(|b| b.is_ascii_punctuation()),
+            );
+    let mut bytes = Vec::with_capacity(num_words * 2);
+    while bytes.len() / 2 < num_words || len < policy.min_len {
+        let words = bytes.len() / 2;
+        check_num_words(words + 1)?;
+        if words > 0 {
+            len += separator_len;
+        }
+        let word = match policy.max_len {
+            None => index_to_word(random_index(rng)?),
+            Some(max_len) => {
+                let later = num_words.saturating_sub(words + 1);
+                let room = max_len.saturating_sub(len + later * (separator_len + shortest));
+                let fits = default_words_up_to_len(room);
+                // Without words still to come, a word must finish the password or leave room
+                // for another one.
+                let (open, finishing) = if later > 0 {
+                    (fits.len(), fits.len())
+                } else {
+                    let open = room
+                        .checked_sub(separator_len + shortest)
+                        .map_or(0, |len| default_words_up_to_len(len).len());
+                    let too_short = policy
+                        .min_len
+                        .checked_sub(len + 1)
+                        .map_or(0, |len| default_words_up_to_len(len).len());
+                    (open, too_short.max(open))
+                };
+                let candidates = open + fits.len() - finishing;
+                if candidates == 0 {
+                    return Err(Error::PolicyViolation {
+                        len: len + shortest,
+                        requirement: "max_len",
+                    });
+                }
+                let pick = random_below(candidates, rng)?;
+                fits[if pick < open {
+                    pick
+                } else {
+                    finishing + pick - open
+                }]
+            }
+        };
+        len += word.chars().count();
+        bytes.extend(&word_to_index(word)?.to_be_bytes());
+    }
+    bytes_to_policy_password(&bytes, policy, separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytes_to_policy_password, generate_policy_password, passphrase_to_bytes};
+    use crate::{Error, Policy};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn decorates_deterministically() {
+        let policy = Policy {
+            require_digit: true,
+            require_symbol: true,
+            require_uppercase: true,
+            ..Default::default()
+        };
+        let bytes = [0x11, 0xd4, 255, 255];
+        let password = bytes_to_policy_password(&bytes, &policy, " ").unwrap();
+        assert_eq!(
+            password,
+            bytes_to_policy_password(&bytes, &policy, " ").unwrap()
+        );
+        assert!(password.starts_with("Bioengineering zyzzyva"));
+        assert_eq!(password.len(), "bioengineering zyzzyva".len() + 2);
+        assert!(policy.allows(&password));
+
+        let words: Vec<&str> = password
+            .trim_end_matches(|c: char| !c.is_ascii_alphabetic())
+            .split(' ')
+            .collect();
+        assert_eq!(passphrase_to_bytes(&words).unwrap(), bytes);
+
+        // The separator already provides a symbol.
+        let password = bytes_to_policy_password(&bytes, &policy, "_").unwrap();
+        assert!(password.starts_with("Bioengineering_zyzzyva"));
+        assert_eq!(password.len(), "bioengineering_zyzzyva".len() + 1);
+
+        assert_eq!(
+            bytes_to_policy_password(&bytes, &Policy::default(), " ").unwrap(),
+            "bioengineering zyzzyva"
+        );
+    }
+
+    #[test]
+    fn checks_lengths() {
+        let policy = Policy {
+            min_len: 30,
+            max_len: Some(40),
+            ..Default::default()
+        };
+        assert!(matches!(
+            bytes_to_policy_password(&[0, 0, 255, 255], &policy, " "),
+            Err(Error::PolicyViolation {
+                len: 9,
+                requirement: "min_len"
+            })
+        ));
+
+        // Windows the words can meet are never missed, however tight.
+        let mut rng = StdRng::seed_from_u64(1);
+        for (num_words, min_len, max_len) in [(1, 30, 40), (4, 0, 30), (3, 30, 30), (2, 12, 13)] {
+            let policy = Policy {
+                min_len,
+                max_len: Some(max_len),
+                require_digit: true,
+                ..Default::default()
+            };
+            for _ in 0..200 {
+                let password = generate_policy_password(num_words, &policy, " ", &mut rng).unwrap();
+                assert!(policy.allows(&password), "{}", password);
+                assert!(password.split(' ').count() >= num_words);
+            }
+        }
+        let policy = Policy {
+            max_len: Some(8),
+            ..Default::default()
+        };
+        assert!(matches!(
+            generate_policy_password(5, &policy, " ", &mut rng),
+            Err(Error::PolicyViolation {
+                requirement: "max_len",
+                ..
+            })
+        ));
+        let policy = Policy {
+            require_uppercase: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            bytes_to_policy_password(&[], &policy, " ")
+                .unwrap_err()
+                .to_string(),
+            "password of length 0 does not meet the uppercase requirement of the policy"
+        );
+
+        let policy = Policy {
+            min_len: 10_000,
+            ..Default::default()
+        };
+        assert!(matches!(
+            generate_policy_password(1, &policy, " ", &mut rng),
+            Err(Error::TooManyWords { .. })
+        ));
+    }
+}
@@ -0,0 +1,305 @@
+use crate::case::capitalize;
+use crate::generate::{check_num_words, random_below, random_index};
+use crate::{EntropySource, Error, WORD_COUNT};
+
+/// Case applied to every word of a diceware passphrase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WordCase {
+    /// All letters lowercase.
+    #[default]
+    Lower,
+    /// All letters uppercase.
+    Upper,
+    /// First letter uppercase, the rest lowercase.
+    Title,
+}
+
+impl WordCase {
+    fn apply(self, word: &str, out: &mut String) {
+        match self {
+            WordCase::Lower => out.extend(word.chars().flat_map(char::to_lowercase)),
+            WordCase::Upper => out.extend(word.chars().flat_map(char::to_uppercase)),
+            WordCase::Title => {
+                let mut chars = word.chars();
+                out.extend(chars.next().into_iter().flat_map(char::to_uppercase));
+                out.extend(chars.flat_map(char::to_lowercase));
+            }
+        }
+    }
+}
+
+/// Passphrase generator settings mirroring KeePassXC's passphrase generator.
+///
+/// KeePassXC draws `word_count` words uniformly and independently from its wordlist, applies the
+/// word case and joins them with the separator; it rates the result at `log2(list length)` bits
+/// per word. With the same settings and list, passphrases from this profile cannot be told apart
+/// from KeePassXC's. This crate does not ship KeePassXC's default list, the EFF large wordlist;
+/// load it and pass it as `wordlist`, or leave `wordlist` empty to use the niceware list.
+///
+/// The default matches KeePassXC's defaults: seven lowercase words separated by spaces.
+///
+/// ## Examples
+///
+/// ```
+/// let profile = niceware::DicewareProfile {
+///     separator: "-",
+///     case: niceware::WordCase::Title,
+///     ..Default::default()
+/// };
+/// let passphrase = profile.generate(&mut rand::thread_rng()).unwrap();
+/// assert_eq!(passphrase.split('-').count(), 7);
+/// assert_eq!(profile.entropy_bits(), 112.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DicewareProfile<'a> {
+    /// Words to draw from; the default wordlist if empty.
+    pub wordlist: &'a [&'a str],
+    /// Number of words in the passphrase.
+    pub word_count: usize,
+    /// Text placed between words.
+    pub separator: &'a str,
+    /// Case applied to every word.
+    pub case: WordCase,
+}
+
+impl Default for DicewareProfile<'_> {
+    fn default() -> Self {
+        DicewareProfile {
+            wordlist: &[],
+            word_count: 7,
+            separator: " ",
+            case: WordCase::Lower,
+        }
+    }
+}
+
+impl DicewareProfile<'_> {
+    /// Entropy of passphrases from this profile in bits, as KeePassXC reports it.
+    pub fn entropy_bits(&self) -> f64 {
+        self.word_count as f64 * (self.list_len() as f64).log2()
+    }
+
+    /// Generate a passphrase with these settings.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns a TooManyWords error if `word_count` is greater than 512, and an
+    /// RNGError if the RNG failed to generate bytes.
+    pub fn generate<R: EntropySource>(&self, rng: &mut R) -> Result<String, Error> {
+        check_num_words(self.word_count)?;
+
+        let mut passphrase = String::new();
+        for i in 0..self.word_count {
+            if i > 0 {
+                passphrase.push_str(self.separator);
+            }
+            let draw = random_below(self.list_len(), rng)?;
+            let word = if self.wordlist.is_empty() {
+                crate::index_to_word(draw as u16)
+            } else {
+                self.wordlist[draw]
+            };
+            self.case.apply(word, &mut passphrase);
+        }
+        Ok(passphrase)
+    }
+
+    fn list_len(&self) -> usize {
+        if self.wordlist.is_empty() {
+            WORD_COUNT
+        } else {
+            self.wordlist.len()
+        }
+    }
+}
+
+/// Which words a memorable password recipe capitalizes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Capitalize {
+    /// Leave every word lowercase.
+    #[default]
+    None,
+    /// Capitalize the first letter of every word.
+    All,
+    /// Capitalize the first letter of one randomly chosen word.
+    One,
+}
+
+/// Recipe for a "memorable password" like those of Bitwarden and 1Password.
+///
+/// The recipe draws `words` niceware words, joins them with `separator`, capitalizes according to
+/// `capitalize`, and with `include_number` appends a random digit to a randomly chosen word.
+/// `entropy_bits` counts everything that is random: 16 bits per word, `log2(words)` for the word
+/// capitalized by `Capitalize::One`, and `log2(10) + log2(words)` for the number and its position.
+/// Capitalizing every word adds nothing.
+///
+/// ## Examples
+///
+/// ```
+/// let recipe = niceware::MemorableRecipe {
+///     words: 4,
+///     separator: "-",
+///     capitalize: niceware::Capitalize::One,
+///     include_number: true,
+/// };
+/// let password = recipe.generate(&mut rand::thread_rng()).unwrap();
+/// assert_eq!(password.split('-').count(), 4);
+/// assert!(password.bytes().any(|b| b.is_ascii_digit()));
+/// assert_eq!(recipe.entropy_bits(), 64.0 + 2.0 + 10f64.log2() + 2.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemorableRecipe<'a> {
+    /// Number of words.
+    pub words: usize,
+    /// Text placed between words.
+    pub separator: &'a str,
+    /// Which words to capitalize.
+    pub capitalize: Capitalize,
+    /// Whether to append a digit to one of the words.
+    pub include_number: bool,
+}
+
+impl MemorableRecipe<'_> {
+    /// Effective entropy of passwords from this recipe in bits.
+    pub fn entropy_bits(&self) -> f64 {
+        let position_bits = if self.words > 0 {
+            (self.words as f64).log2()
+        } else {
+            0.0
+        };
+        let mut bits = 16.0 * self.words as f64;
+        if self.capitalize == Capitalize::One {
+            bits += position_bits;
+        }
+        if self.include_number && self.words > 0 {
+            bits += 10f64.log2() + position_bits;
+        }
+        bits
+    }
+
+    /// Generate a password following the recipe.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns a TooManyWords error if `words` is greater than 512, and an RNGError
+    /// if the RNG failed to generate bytes.
+    pub fn generate<R: EntropySource>(&self, rng: &mut R) -> Result<String, Error> {
+        check_num_words(self.words)?;
+
+        let mut chosen = Vec::with_capacity(self.words);
+        for _ in 0..self.words {
+            chosen.push(crate::index_to_word(random_index(rng)?).to_string());
+        }
+        if self.words > 0 && self.capitalize == Capitalize::One {
+            let i = random_below(self.words, rng)?;
+            chosen[i] = capitalize(&chosen[i]);
+        }
+        if self.capitalize == Capitalize::All {
+            chosen.iter_mut().for_each(|word| *word = capitalize(word));
+        }
+        if self.words > 0 && self.include_number {
+            let i = random_below(self.words, rng)?;
+            let digit = random_below(10, rng)?;
+            chosen[i].push(char::from(b'0' + digit as u8));
+        }
+        Ok(chosen.join(self.separator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        passphrase_to_bytes, Capitalize, DicewareProfile, Error, MemorableRecipe, WordCase,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn follows_settings() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let dice = ["abacus", "Ángel", "bravo", "zulu"];
+        let profile = DicewareProfile {
+            wordlist: &dice,
+            word_count: 5,
+            separator: ".",
+            case: WordCase::Upper,
+        };
+        assert_eq!(profile.entropy_bits(), 10.0);
+        let passphrase = profile.generate(&mut rng).unwrap();
+        let words: Vec<&str> = passphrase.split('.').collect();
+        assert_eq!(words.len(), 5);
+        assert!(words
+            .iter()
+            .all(|word| dice.iter().any(|d| d.to_uppercase() == *word)));
+
+        let title = DicewareProfile {
+            wordlist: &["ÁNGEL"],
+            case: WordCase::Title,
+            ..Default::default()
+        };
+        assert_eq!(title.generate(&mut rng).unwrap(), ["Ángel"; 7].join(" "));
+        assert_eq!(title.entropy_bits(), 0.0);
+    }
+
+    #[test]
+    fn defaults_to_niceware_list() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let profile = DicewareProfile::default();
+        assert_eq!(profile.entropy_bits(), 112.0);
+        let passphrase = profile.generate(&mut rng).unwrap();
+        let words: Vec<&str> = passphrase.split(' ').collect();
+        assert_eq!(passphrase_to_bytes(&words).unwrap().len(), 14);
+
+        let too_long = DicewareProfile {
+            word_count: 513,
+            ..Default::default()
+        };
+        assert!(matches!(
+            too_long.generate(&mut rng),
+            Err(Error::TooManyWords { .. })
+        ));
+    }
+
+    #[test]
+    fn memorable_recipes() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut recipe = MemorableRecipe {
+            words: 3,
+            separator: " ",
+            capitalize: Capitalize::None,
+            include_number: false,
+        };
+        assert_eq!(recipe.entropy_bits(), 48.0);
+        let password = recipe.generate(&mut rng).unwrap();
+        let words: Vec<&str> = password.split(' ').collect();
+        assert_eq!(passphrase_to_bytes(&words).unwrap().len(), 6);
+        assert!(password
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b == b' '));
+
+        recipe.capitalize = Capitalize::All;
+        assert_eq!(recipe.entropy_bits(), 48.0);
+        let password = recipe.generate(&mut rng).unwrap();
+        assert!(password
+            .split(' ')
+            .all(|word| word.starts_with(|c: char| c.is_ascii_uppercase())));
+
+        recipe.capitalize = Capitalize::One;
+        recipe.include_number = true;
+        assert_eq!(
+            recipe.entropy_bits(),
+            48.0 + 2.0 * 3f64.log2() + 10f64.log2()
+        );
+        let password = recipe.generate(&mut rng).unwrap();
+        assert_eq!(password.bytes().filter(u8::is_ascii_uppercase).count(), 1);
+        assert_eq!(password.bytes().filter(u8::is_ascii_digit).count(), 1);
+        let words: Vec<&str> = password
+            .split(' ')
+            .map(|word| word.trim_end_matches(|c: char| c.is_ascii_digit()))
+            .collect();
+        assert_eq!(passphrase_to_bytes(&words).unwrap().len(), 6);
+
+        recipe.words = 0;
+        assert_eq!(recipe.entropy_bits(), 0.0);
+        assert_eq!(recipe.generate(&mut rng).unwrap(), "");
+    }
+}
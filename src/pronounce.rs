@@ -0,0 +1,144 @@
+use crate::generate::{check_num_words, random_below};
+use crate::wordlist::default_words;
+use crate::{EntropySource, Error, Generated};
+
+/// Score how hard a word is to pronounce; zero means easy.
+///
+/// The score adds one point for every consonant sound beyond the second in a run of consonants
+/// ("strengths" scores 2; double letters and digraphs such as "th" or "ng" are one sound), one for a `q` not followed by `u`, one for every `j`, `v`, `x` or `z`
+/// directly followed by another consonant, and one for every syllable (vowel group) beyond the
+/// fourth. A `y` counts as a vowel unless it starts the word. The score is a heuristic for
+/// English speakers, not a linguistic model.
+///
+/// ## Examples
+///
+/// ```
+/// assert_eq!(niceware::pronunciation_difficulty("balloted"), 0);
+/// assert!(niceware::pronunciation_difficulty("zyzzyva") > 0);
+/// ```
+pub fn pronunciation_difficulty(word: &str) -> u32 {
+    let letters = word.as_bytes();
+    let is_vowel = |i: usize| is_vowel(letters, i);
+
+    let mut score = 0;
+    let mut run = 0;
+    let mut syllables = 0u32;
+    for i in 0..letters.len() {
+        let c = letters[i].to_ascii_lowercase();
+        let next_is_consonant = i + 1 < letters.len() && !is_vowel(i + 1);
+        if is_vowel(i) {
+            if run > 0 || i == 0 {
+                syllables += 1;
+            }
+            run = 0;
+            continue;
+        }
+        let previous = i.checked_sub(1).map(|j| letters[j].to_ascii_lowercase());
+        let one_sound = previous.is_some_and(|p| {
+            p == c
+                || matches!(
+                    [p, c],
+                    [b'c' | b'p' | b's' | b't' | b'w' | b'g', b'h'] | [b'c', b'k'] | [b'n', b'g']
+                )
+        });
+        if !one_sound {
+            run += 1;
+            if run > 2 {
+                score += 1;
+            }
+        }
+        if c == b'q' && letters.get(i + 1).map(u8::to_ascii_lowercase) != Some(b'u') {
+            score += 1;
+        }
+        if matches!(c, b'j' | b'v' | b'x' | b'z') && next_is_consonant {
+            score += 1;
+        }
+    }
+    score + syllables.saturating_sub(4)
+}
+
+/// Whether the letter at `i` is a vowel: `a`, `e`, `i`, `o`, `u`, or a `y` that does not start the
+/// word.
+pub(crate) fn is_vowel(letters: &[u8], i: usize) -> bool {
+    let c = letters[i].to_ascii_lowercase();
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u') || (c == b'y' && i > 0)
+}
+
+/// Generate a passphrase of words scoring at most `max_difficulty` with `pronunciation_difficulty`.
+///
+/// Meant for phrases that are read aloud, such as support or pairing codes. Words above the limit
+/// are never drawn, so every word is uniform over the remaining ones and the result reports
+/// `log2(remaining)` bits per word. With a limit of 0 about 52,600 words remain, costing about a
+/// third of a bit per word; a limit of 1 keeps about 62,600.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 512, and an RNGError
+/// if the RNG failed to generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let generated =
+///     niceware::generate_pronounceable_passphrase(4, 0, &mut rand::thread_rng()).unwrap();
+/// assert!(generated
+///     .words
+///     .iter()
+///     .all(|word| niceware::pronunciation_difficulty(word) == 0));
+/// assert!(generated.entropy_bits > 60.0);
+/// ```
+pub fn generate_pronounceable_passphrase<R: EntropySource>(
+    num_words: usize,
+    max_difficulty: u32,
+    rng: &mut R,
+) -> Result<Generated, Error> {
+    check_num_words(num_words)?;
+
+    let candidates: Vec<&'static str> = default_words()
+        .filter(|word| pronunciation_difficulty(word) <= max_difficulty)
+        .collect();
+    let chosen = (0..num_words)
+        .map(|_| Ok(candidates[random_below(candidates.len(), rng)?]))
+        .collect::<Result<_, Error>>()?;
+
+    Ok(Generated {
+        words: chosen,
+        entropy_bits: num_words as f64 * (candidates.len() as f64).log2(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{generate_pronounceable_passphrase, pronunciation_difficulty, words};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn scores() {
+        assert_eq!(pronunciation_difficulty("a"), 0);
+        assert_eq!(pronunciation_difficulty("gobbled"), 0);
+        assert_eq!(pronunciation_difficulty("strengths"), 2);
+        assert_eq!(pronunciation_difficulty("Strengths"), 2);
+        assert_eq!(pronunciation_difficulty("qat"), 1);
+        assert_eq!(pronunciation_difficulty("quiet"), 0);
+        assert_eq!(pronunciation_difficulty("yes"), 0);
+        // Seven vowel groups, three beyond the fourth, and the cluster "nst".
+        assert_eq!(pronunciation_difficulty("unconstitutionally"), 4);
+    }
+
+    #[test]
+    fn generates_easy_words() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let generated = generate_pronounceable_passphrase(32, 0, &mut rng).unwrap();
+        assert_eq!(generated.words.len(), 32);
+        assert!(generated
+            .words
+            .iter()
+            .all(|word| pronunciation_difficulty(word) == 0));
+
+        let easy = words()
+            .filter(|word| pronunciation_difficulty(word) == 0)
+            .count();
+        assert_eq!(generated.entropy_bits, 32.0 * (easy as f64).log2());
+        assert!(generate_pronounceable_passphrase(513, 0, &mut rng).is_err());
+    }
+}
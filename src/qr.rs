@@ -0,0 +1,134 @@
+//! QR codes of passphrases, enabled by the `qr` feature.
+
+use crate::{index_to_word, split_words, word_to_index, Error};
+use qrcode::render::svg;
+use qrcode::{Color, QrCode};
+
+/// Largest passphrase text, in bytes, that fits in a QR code at the error correction level used.
+const MAX_QR_TEXT: usize = 2331;
+
+/// Width of the light border around the code, in modules, as required by the QR specification.
+const QUIET_ZONE: usize = 4;
+
+/// Render a passphrase as an SVG image of a QR code.
+///
+/// The code contains the canonical passphrase text (lowercase words separated by single spaces),
+/// so any scanner shows something a person can read back, and `scanned_text_to_bytes` decodes it.
+/// To share raw bytes, encode them with `bytes_to_passphrase` first.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary, and a
+/// PayloadTooLarge error if the text does not fit in a QR code.
+pub fn phrase_to_qr_svg(words: &[&str]) -> Result<String, Error> {
+    Ok(qr_code(words)?
+        .render::<svg::Color>()
+        .quiet_zone(true)
+        .build())
+}
+
+/// Render a passphrase as a grayscale PNG image of a QR code, `module_size` pixels per module.
+///
+/// The code contains the same text as with `phrase_to_qr_svg`.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary, and a
+/// PayloadTooLarge error if the text does not fit in a QR code.
+///
+/// ## Panics
+///
+/// Panics if `module_size` is zero.
+pub fn phrase_to_qr_png(words: &[&str], module_size: u32) -> Result<Vec<u8>, Error> {
+    assert!(module_size > 0, "module size must not be zero");
+    let code = qr_code(words)?;
+    let colors = code.to_colors();
+    let modules = code.width() + 2 * QUIET_ZONE;
+    let scale = module_size as usize;
+    let side = modules * scale;
+
+    let mut pixels = vec![u8::MAX; side * side];
+    for (i, _) in colors.iter().enumerate().filter(|(_, &c)| c == Color::Dark) {
+        let (x, y) = (i % code.width() + QUIET_ZONE, i / code.width() + QUIET_ZONE);
+        for row in y * scale..(y + 1) * scale {
+            pixels[row * side + x * scale..row * side + (x + 1) * scale].fill(0);
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, side as u32, side as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .expect("encoding a PNG into memory cannot fail");
+    Ok(png)
+}
+
+/// Decode the text read from a scanned QR code into bytes.
+///
+/// Scanners differ in the line endings and spacing they return, so words may be separated by any
+/// whitespace, commas or hyphens, and are matched ignoring ASCII case.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary.
+pub fn scanned_text_to_bytes(text: &str) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(text.len() / 4);
+    for word in split_words(text) {
+        bytes.extend(&word_to_index(word)?.to_be_bytes());
+    }
+    Ok(bytes)
+}
+
+fn qr_code(phrase: &[&str]) -> Result<QrCode, Error> {
+    let mut text = String::new();
+    for word in phrase {
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(index_to_word(word_to_index(word)?));
+    }
+    QrCode::new(&text).map_err(|_| Error::PayloadTooLarge {
+        size: text.len(),
+        max_size: MAX_QR_TEXT,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytes_to_passphrase, phrase_to_qr_png, phrase_to_qr_svg, scanned_text_to_bytes};
+
+    const BYTES: [u8; 8] = [0, 0, 17, 212, 12, 140, 90, 246];
+
+    #[test]
+    fn renders_images() {
+        let words = bytes_to_passphrase(&BYTES).unwrap();
+        let svg = phrase_to_qr_svg(&words).unwrap();
+        assert!(svg.contains("<svg"));
+
+        let png = phrase_to_qr_png(&words, 3).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        // A version 3 code is 29 modules wide, plus the quiet zone on both sides.
+        assert_eq!(&png[16..24], &[0, 0, 0, 111, 0, 0, 0, 111]);
+
+        assert_eq!(
+            phrase_to_qr_svg(&["A", "ninetales"])
+                .unwrap_err()
+                .to_string(),
+            "unknown word: ninetales"
+        );
+        let too_long = vec!["bioengineering"; 512];
+        assert!(phrase_to_qr_png(&too_long, 1).is_err());
+    }
+
+    #[test]
+    fn decodes_scanned_text() {
+        assert_eq!(
+            scanned_text_to_bytes("A bioengineering\r\n balloted  gobbled\n").unwrap(),
+            BYTES
+        );
+        assert!(scanned_text_to_bytes("").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,458 @@
+use crate::{words, Error};
+
+/// Converts bytes to and from sequences over an arbitrary symbol table.
+///
+/// Bytes are read as one big-endian number and written in base `symbols.len()`, most significant
+/// digit first. The number of digits only depends on the number of bytes, so leading zero bytes
+/// are preserved. With the 2^16 niceware words as symbols this is exactly niceware's encoding of
+/// even-sized input, see `RadixCodec::niceware`.
+///
+/// Tables of at most 256 symbols always round-trip through `encode` and `decode`. Larger tables
+/// can map two byte lengths to the same number of symbols (one byte and two bytes both take one
+/// niceware word); `decode` then assumes the longer one and `decode_exact` takes the length from
+/// the caller.
+///
+/// Tables whose size is a power of two, niceware's included, convert by packing bits in linear
+/// time; the crate's own encoding runs on the same path. Other tables take time quadratic in the
+/// input length, which is fine for keys and passphrases but not for bulk data.
+///
+/// ## Examples
+///
+/// ```
+/// let colors = ["red", "green", "blue"];
+/// let codec = niceware::RadixCodec::new(&colors).unwrap();
+/// let encoded = codec.encode(&[7]);
+/// assert_eq!(encoded, &["red", "red", "red", "red", "blue", "green"]);
+/// assert_eq!(codec.decode(&encoded).unwrap(), &[7]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RadixCodec<'a, T> {
+    symbols: &'a [T],
+}
+
+impl RadixCodec<'static, &'static str> {
+    /// Codec over the niceware wordlist.
+    pub fn niceware() -> Self {
+        RadixCodec {
+            symbols: words::ALL_WORDS,
+        }
+    }
+}
+
+impl<'a, T> RadixCodec<'a, T> {
+    /// Create a codec over the given symbols.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an InvalidSymbolCount error if there are fewer than 2 or more than
+    /// 2^32 symbols.
+    pub fn new(symbols: &'a [T]) -> Result<Self, Error> {
+        if symbols.len() < 2 || symbols.len() as u64 > 1 << 32 {
+            return Err(Error::InvalidSymbolCount {
+                count: symbols.len(),
+            });
+        }
+        Ok(RadixCodec { symbols })
+    }
+
+    /// The symbol table.
+    pub fn symbols(&self) -> &'a [T] {
+        self.symbols
+    }
+
+    /// Number of symbols `encode` produces for the given number of bytes.
+    pub fn encoded_len(&self, byte_len: usize) -> usize {
+        encoded_len(self.base(), byte_len)
+    }
+
+    /// Number of bytes `decode` produces for the given number of symbols.
+    pub fn decoded_len(&self, symbol_len: usize) -> usize {
+        decoded_len(self.base(), symbol_len)
+    }
+
+    /// Encode bytes into indices into the symbol table.
+    pub fn encode_indices(&self, bytes: &[u8]) -> Vec<usize> {
+        let base = self.base();
+        if base == 1 << 16 && bytes.len() % 2 == 0 {
+            word_digits(bytes).map(usize::from).collect()
+        } else if base.is_power_of_two() {
+            bit_digits(bytes, base.trailing_zeros())
+                .map(|digit| digit as usize)
+                .collect()
+        } else {
+            encode_limbs(base, bytes)
+        }
+    }
+
+    /// Encode bytes into symbols.
+    pub fn encode(&self, bytes: &[u8]) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.encode_indices(bytes)
+            .into_iter()
+            .map(|index| self.symbols[index].clone())
+            .collect()
+    }
+
+    /// Decode indices into the symbol table into `decoded_len(indices.len())` bytes.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an UnknownSymbol error if an index is out of range for the symbol
+    /// table, and an Overflow error if the indices encode a value that does not fit.
+    pub fn decode_indices(&self, indices: &[usize]) -> Result<Vec<u8>, Error> {
+        self.decode_indices_exact(indices, self.decoded_len(indices.len()))
+    }
+
+    /// Decode indices into the symbol table into exactly `byte_len` bytes.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an UnknownSymbol error if an index is out of range for the symbol
+    /// table, and an Overflow error if the indices encode a value that does not fit.
+    pub fn decode_indices_exact(
+        &self,
+        indices: &[usize],
+        byte_len: usize,
+    ) -> Result<Vec<u8>, Error> {
+        if let Some(position) = indices
+            .iter()
+            .position(|&index| index >= self.symbols.len())
+        {
+            return Err(Error::UnknownSymbol { position });
+        }
+        let base = self.base();
+        if base.is_power_of_two() {
+            decode_bits(indices, base.trailing_zeros(), byte_len)
+        } else {
+            decode_limbs(base, indices, byte_len)
+        }
+    }
+
+    /// Decode symbols into `decoded_len(symbols.len())` bytes.
+    ///
+    /// Symbols are looked up with a linear scan of the table. For large tables, keep an index of
+    /// your own and call `decode_indices` instead.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an UnknownSymbol error if a symbol is not in the table, and an
+    /// Overflow error if the symbols encode a value that does not fit.
+    pub fn decode<Q>(&self, symbols: &[Q]) -> Result<Vec<u8>, Error>
+    where
+        T: PartialEq<Q>,
+    {
+        self.decode_exact(symbols, self.decoded_len(symbols.len()))
+    }
+
+    /// Decode symbols into exactly `byte_len` bytes.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an UnknownSymbol error if a symbol is not in the table, and an
+    /// Overflow error if the symbols encode a value that does not fit.
+    pub fn decode_exact<Q>(&self, symbols: &[Q], byte_len: usize) -> Result<Vec<u8>, Error>
+    where
+        T: PartialEq<Q>,
+    {
+        let indices = symbols
+            .iter()
+            .enumerate()
+            .map(|(position, symbol)| {
+                self.symbols
+                    .iter()
+                    .position(|candidate| candidate == symbol)
+                    .ok_or(Error::UnknownSymbol { position })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.decode_indices_exact(&indices, byte_len)
+    }
+
+    fn base(&self) -> u64 {
+        self.symbols.len() as u64
+    }
+}
+
+/// Smallest n with base^n >= 256^byte_len.
+fn encoded_len(base: u64, byte_len: usize) -> usize {
+    if base.is_power_of_two() {
+        let bits = base.trailing_zeros() as usize;
+        return (byte_len * 8).div_ceil(bits);
+    }
+    // base^n is never a power of 256 here, so this is the smallest n with more than byte_len bytes.
+    let mut power = vec![1u8];
+    let mut n = 0;
+    while power.len() <= byte_len {
+        multiply(&mut power, base);
+        n += 1;
+    }
+    n
+}
+
+/// Largest n with 256^n <= base^symbol_len.
+fn decoded_len(base: u64, symbol_len: usize) -> usize {
+    if base.is_power_of_two() {
+        return symbol_len * base.trailing_zeros() as usize / 8;
+    }
+    let mut power = vec![1u8];
+    for _ in 0..symbol_len {
+        multiply(&mut power, base);
+    }
+    power.len() - 1
+}
+
+/// Multiply a little-endian number in place, growing it as needed.
+fn multiply(number: &mut Vec<u8>, factor: u64) {
+    let mut carry = 0;
+    for byte in number.iter_mut() {
+        let acc = u64::from(*byte) * factor + carry;
+        *byte = acc as u8;
+        carry = acc >> 8;
+    }
+    while carry != 0 {
+        number.push(carry as u8);
+        carry >>= 8;
+    }
+}
+
+/// Digits of the big-endian number `bytes` in base 2^`bits`, most significant first.
+///
+/// This is the linear path for power-of-two tables, niceware's 16-bit words among them. The
+/// number of digits is `encoded_len`, so a partial digit at the top holds the leftover high bits.
+pub(crate) fn bit_digits(bytes: &[u8], bits: u32) -> BitDigits<'_> {
+    debug_assert!((1..=32).contains(&bits));
+    let len = encoded_len(1 << bits, bytes.len());
+    BitDigits {
+        bytes,
+        bits: bits as usize,
+        pad: len * bits as usize - bytes.len() * 8,
+        next: 0,
+        len,
+    }
+}
+
+/// Digits of `bytes` in base 2^16, the niceware word indices; `bytes` must have an even length.
+///
+/// This is the byte-aligned case of `bit_digits`, kept separate because the crate's own encoding
+/// runs on it.
+pub(crate) fn word_digits(bytes: &[u8]) -> impl Iterator<Item = u16> + Clone + '_ {
+    debug_assert!(bytes.len() % 2 == 0);
+    bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+}
+
+/// Iterator returned by `bit_digits`.
+#[derive(Clone, Debug)]
+pub(crate) struct BitDigits<'b> {
+    bytes: &'b [u8],
+    bits: usize,
+    /// Zero bits in front of the number that fill up the top digit.
+    pad: usize,
+    next: usize,
+    len: usize,
+}
+
+impl Iterator for BitDigits<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.next == self.len {
+            return None;
+        }
+        // Bit range of the digit within `bytes`, counted from the most significant bit.
+        let start = (self.next * self.bits).saturating_sub(self.pad);
+        let end = (self.next + 1) * self.bits - self.pad;
+        let window = self.bytes[start / 8..end.div_ceil(8)]
+            .iter()
+            .fold(0u64, |acc, &byte| acc << 8 | u64::from(byte));
+        self.next += 1;
+        Some(window >> ((8 - end % 8) % 8) & ((1 << self.bits) - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for BitDigits<'_> {}
+
+/// Pack digits in base 2^`bits` into `byte_len` big-endian bytes.
+fn decode_bits(digits: &[usize], bits: u32, byte_len: usize) -> Result<Vec<u8>, Error> {
+    let mut bytes = vec![0u8; byte_len];
+    let mut out = bytes.iter_mut().rev();
+    let mut acc = 0u64;
+    let mut acc_bits = 0;
+    for &digit in digits.iter().rev() {
+        acc |= (digit as u64) << acc_bits;
+        acc_bits += bits;
+        while acc_bits >= 8 {
+            match out.next() {
+                Some(byte) => *byte = acc as u8,
+                None if acc as u8 != 0 => return Err(Error::Overflow { size: byte_len }),
+                None => {}
+            }
+            acc >>= 8;
+            acc_bits -= 8;
+        }
+    }
+    if acc != 0 {
+        match out.next() {
+            Some(byte) => *byte = acc as u8,
+            None => return Err(Error::Overflow { size: byte_len }),
+        }
+    }
+    Ok(bytes)
+}
+
+/// Digits of the big-endian number `bytes` in any base, most significant first.
+///
+/// The number is kept as little-endian 32-bit limbs and divided by the base once per digit,
+/// dropping limbs as they become zero. This takes time quadratic in the length of `bytes`.
+fn encode_limbs(base: u64, bytes: &[u8]) -> Vec<usize> {
+    let mut limbs: Vec<u32> = bytes
+        .rchunks(4)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0, |acc, &byte| acc << 8 | u32::from(byte))
+        })
+        .collect();
+    let mut digits = vec![0; encoded_len(base, bytes.len())];
+    for digit in digits.iter_mut().rev() {
+        let mut remainder = 0;
+        for limb in limbs.iter_mut().rev() {
+            let acc = remainder << 32 | u64::from(*limb);
+            *limb = (acc / base) as u32;
+            remainder = acc % base;
+        }
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        *digit = remainder as usize;
+    }
+    digits
+}
+
+/// Multiply digits in any base into `byte_len` big-endian bytes.
+fn decode_limbs(base: u64, digits: &[usize], byte_len: usize) -> Result<Vec<u8>, Error> {
+    let mut limbs: Vec<u32> = Vec::new();
+    for &digit in digits {
+        let mut carry = digit as u64;
+        for limb in limbs.iter_mut() {
+            let acc = u64::from(*limb) * base + carry;
+            *limb = acc as u32;
+            carry = acc >> 32;
+        }
+        if carry != 0 {
+            limbs.push(carry as u32);
+        }
+    }
+
+    let mut bytes = vec![0u8; byte_len];
+    let mut out = bytes.iter_mut().rev();
+    for byte in limbs.iter().flat_map(|limb| limb.to_le_bytes()) {
+        match out.next() {
+            Some(slot) => *slot = byte,
+            None if byte != 0 => return Err(Error::Overflow { size: byte_len }),
+            None => {}
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::radix::{bit_digits, decode_bits, decode_limbs, encode_limbs};
+    use crate::{bytes_to_passphrase, Error, RadixCodec};
+
+    #[test]
+    fn niceware_instance() {
+        let codec = RadixCodec::niceware();
+        let bytes = [
+            0, 0, 17, 212, 12, 140, 90, 246, 46, 83, 254, 60, 54, 169, 255, 255,
+        ];
+        let words = codec.encode(&bytes);
+        assert_eq!(words, bytes_to_passphrase(&bytes).unwrap());
+        assert_eq!(codec.decode(&words[..3]).unwrap(), &bytes[..6]);
+        assert_eq!(codec.encoded_len(1), 1);
+        assert_eq!(codec.decoded_len(1), 2);
+    }
+
+    #[test]
+    fn small_tables_round_trip() {
+        let bytes: Vec<u8> = (0..40).map(|i| (i * 37 % 256) as u8).collect();
+        for base in [2, 3, 10, 58, 255, 256] {
+            let symbols: Vec<u32> = (0..base).collect();
+            let codec = RadixCodec::new(&symbols).unwrap();
+            for len in 0..bytes.len() {
+                let encoded = codec.encode_indices(&bytes[..len]);
+                assert_eq!(encoded.len(), codec.encoded_len(len));
+                assert_eq!(codec.decode_indices(&encoded).unwrap(), &bytes[..len]);
+            }
+        }
+    }
+
+    #[test]
+    fn bit_packing_matches_limbs() {
+        let bytes: Vec<u8> = (0..40).map(|i| (i * 37 % 256) as u8).collect();
+        for bits in [1, 3, 5, 8, 13, 16, 20, 32] {
+            let base = 1u64 << bits;
+            for len in 0..bytes.len() {
+                let digits: Vec<usize> = bit_digits(&bytes[..len], bits)
+                    .map(|digit| digit as usize)
+                    .collect();
+                assert_eq!(digits, encode_limbs(base, &bytes[..len]));
+                assert_eq!(
+                    decode_bits(&digits, bits, len).unwrap(),
+                    decode_limbs(base, &digits, len).unwrap()
+                );
+            }
+        }
+
+        let hex: Vec<u32> = (0..16).collect();
+        let codec = RadixCodec::new(&hex).unwrap();
+        assert_eq!(codec.encode_indices(&[0x4f, 0x01]), &[4, 15, 0, 1]);
+        assert!(matches!(
+            codec.decode_indices_exact(&[1, 0, 0], 1),
+            Err(Error::Overflow { size: 1 })
+        ));
+    }
+
+    #[test]
+    fn leading_zeros_are_kept() {
+        let symbols: Vec<u32> = (0..10).collect();
+        let codec = RadixCodec::new(&symbols).unwrap();
+        assert_eq!(codec.encode_indices(&[0, 0, 1]), &[0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(codec.encode_indices(&[255]), &[2, 5, 5]);
+        assert_eq!(
+            codec.decode_indices(&[0, 0, 0, 0, 0, 0, 0, 1]).unwrap(),
+            &[0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            RadixCodec::new(&["only"]),
+            Err(Error::InvalidSymbolCount { count: 1 })
+        ));
+
+        let codec = RadixCodec::new(&["a", "b", "c"]).unwrap();
+        assert_eq!(
+            codec.decode(&["a", "d"]).unwrap_err().to_string(),
+            "unknown symbol at position 1"
+        );
+        assert!(matches!(
+            codec.decode_indices(&[0, 3]),
+            Err(Error::UnknownSymbol { position: 1 })
+        ));
+        // 3^6 - 1 = 728 does not fit in one byte.
+        assert_eq!(
+            codec.decode(&["c"; 6]).unwrap_err().to_string(),
+            "symbols do not fit in 1 bytes"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+/// Width of the randomart field, as in OpenSSH.
+const WIDTH: usize = 17;
+
+/// Height of the randomart field, as in OpenSSH.
+const HEIGHT: usize = 9;
+
+/// Characters for cells visited 0 to 14 or more times, then the start and end markers.
+const SYMBOLS: &[u8] = b" .o+=*BOX@%&#/^SE";
+
+/// Draw bytes as OpenSSH-style randomart.
+///
+/// This is the "drunken bishop" walk of `ssh-keygen -lv`, so the same bytes give the same picture
+/// as OpenSSH draws for a fingerprint. It gives people a second, visual way to confirm they
+/// generated or entered the same passphrase as before. `title` and `footer` are shown in brackets
+/// in the top and bottom border, truncated to fit; empty strings leave the border plain.
+///
+/// ## Examples
+///
+/// ```
+/// let art = niceware::randomart(&[0, 17, 212, 12], "niceware", "");
+/// assert_eq!(art.lines().count(), 11);
+/// assert_eq!(art.lines().next(), Some("+---[niceware]----+"));
+/// ```
+pub fn randomart(bytes: &[u8], title: &str, footer: &str) -> String {
+    let mut field = [[0u8; WIDTH]; HEIGHT];
+    let (mut x, mut y) = (WIDTH / 2, HEIGHT / 2);
+    let busiest = SYMBOLS.len() as u8 - 3;
+
+    for &byte in bytes {
+        for step in 0..4 {
+            let bits = byte >> (2 * step);
+            x = if bits & 1 != 0 {
+                (x + 1).min(WIDTH - 1)
+            } else {
+                x.saturating_sub(1)
+            };
+            y = if bits & 2 != 0 {
+                (y + 1).min(HEIGHT - 1)
+            } else {
+                y.saturating_sub(1)
+            };
+            if field[y][x] < busiest {
+                field[y][x] += 1;
+            }
+        }
+    }
+    field[HEIGHT / 2][WIDTH / 2] = busiest + 1;
+    field[y][x] = busiest + 2;
+
+    let mut art = border(title);
+    for row in &field {
+        art.push('|');
+        art.extend(
+            row.iter()
+                .map(|&cell| char::from(SYMBOLS[usize::from(cell)])),
+        );
+        art.push_str("|\n");
+    }
+    art.push_str(&border(footer));
+    art
+}
+
+/// A horizontal border with a centered label in brackets, ending in a newline.
+fn border(label: &str) -> String {
+    let label: String = label.chars().take(WIDTH - 2).collect();
+    let label = if label.is_empty() {
+        label
+    } else {
+        format!("[{}]", label)
+    };
+    let left = (WIDTH - label.chars().count()) / 2;
+    let right = WIDTH - label.chars().count() - left;
+    format!("+{}{}{}+\n", "-".repeat(left), label, "-".repeat(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::randomart;
+
+    #[test]
+    fn matches_openssh() {
+        // SHA-256 fingerprint of an Ed25519 key, drawn by `ssh-keygen -lv -E sha256`.
+        let fingerprint = [
+            38, 164, 220, 0, 215, 248, 105, 18, 38, 254, 240, 104, 30, 151, 107, 178, 150, 195, 83,
+            164, 103, 186, 228, 60, 44, 103, 173, 253, 43, 79, 250, 219,
+        ];
+        let expected = "\
++--[ED25519 256]--+
+|  . .o           |
+|  .o+ .          |
+| . o.o..         |
+|  o.o=+          |
+|   Bo+o S        |
+|  = O  o         |
+| =.X ..          |
+|.+#.=o .         |
+| =*Xo=*oE        |
++----[SHA256]-----+
+";
+        assert_eq!(randomart(&fingerprint, "ED25519 256", "SHA256"), expected);
+    }
+
+    #[test]
+    fn borders() {
+        let art = randomart(&[], "", "a title that is far too long");
+        let lines: Vec<&str> = art.lines().collect();
+        assert_eq!(lines[0], "+-----------------+");
+        assert_eq!(lines[5], "|        E        |");
+        assert_eq!(lines[10], "+[a title that is]+");
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    bytes_to_checked_passphrase, bytes_to_passphrase, checked_passphrase_to_bytes,
+    english_passphrase_to_bytes, english_word_to_index, index_to_word, passphrase_to_bytes,
+    wordlist_digest, Error, UPSTREAM_WORDLIST_DIGEST,
+};
+
+/// Bytes of the known-answer vectors.
+const BYTES: [u8; 16] = [
+    0, 0, 17, 212, 12, 140, 90, 246, 46, 83, 254, 60, 54, 169, 255, 255,
+];
+
+/// Words `BYTES` encode to, as in the original JavaScript implementation.
+const WORDS: [&str; 8] = [
+    "a",
+    "bioengineering",
+    "balloted",
+    "gobbled",
+    "creneled",
+    "written",
+    "depriving",
+    "zyzzyva",
+];
+
+/// Checked passphrase of the first five words of `BYTES`, with a check word after each group.
+const CHECKED_WORDS: [&str; 7] = [
+    "a",
+    "bioengineering",
+    "balloted",
+    "gobbled",
+    "autodidact",
+    "creneled",
+    "kilted",
+];
+
+/// Run embedded known-answer tests of the wordlist and the encodings.
+///
+/// This checks the digest of the compiled-in wordlist, encoding and decoding of a fixed phrase in
+/// both directions, case-insensitive decoding, and that check words are computed the same way and
+/// catch a changed word. If a wordlist was registered with `set_default_wordlist`, the encodings
+/// are checked with it, using its words at the indices of the English vectors. Air-gapped deployments can run it before a key ceremony to confirm that
+/// the binary they carried over was not corrupted or tampered with in a way that changes output.
+///
+/// ## Errors
+///
+/// This function returns a SelfTestFailed error naming the first check that failed.
+///
+/// ## Examples
+///
+/// ```
+/// niceware::self_test().unwrap();
+/// ```
+pub fn self_test() -> Result<(), Error> {
+    check(
+        "wordlist digest",
+        wordlist_digest() == UPSTREAM_WORDLIST_DIGEST,
+    )?;
+    check(
+        "english wordlist",
+        english_passphrase_to_bytes(&WORDS).ok().as_deref() == Some(&BYTES[..]),
+    )?;
+    let words = default_words(&WORDS);
+    check(
+        "bytes to passphrase",
+        bytes_to_passphrase(&BYTES).ok().as_ref() == Some(&words),
+    )?;
+    check(
+        "passphrase to bytes",
+        passphrase_to_bytes(&words).ok().as_deref() == Some(&BYTES[..]),
+    )?;
+    check(
+        "case-insensitive decoding",
+        english_passphrase_to_bytes(&["A", "BioEngineering", "ZYZZYVA"]).ok()
+            == Some(vec![0, 0, 17, 212, 255, 255]),
+    )?;
+    let checked = default_words(&CHECKED_WORDS);
+    check(
+        "check words",
+        bytes_to_checked_passphrase(&BYTES[..10]).ok().as_ref() == Some(&checked),
+    )?;
+    check(
+        "checked passphrase to bytes",
+        checked_passphrase_to_bytes(&checked).ok().as_deref() == Some(&BYTES[..10]),
+    )?;
+    let mut changed = checked;
+    changed[1] = changed[2];
+    check(
+        "check word mismatch",
+        matches!(
+            checked_passphrase_to_bytes(&changed),
+            Err(Error::ChecksumMismatch { ref groups }) if groups[..] == [0]
+        ),
+    )
+}
+
+/// Words of the default wordlist at the indices of the given English words.
+fn default_words(english: &[&str]) -> Vec<&'static str> {
+    english
+        .iter()
+        .filter_map(|word| english_word_to_index(word).ok())
+        .map(index_to_word)
+        .collect()
+}
+
+fn check(name: &'static str, passed: bool) -> Result<(), Error> {
+    if passed {
+        Ok(())
+    } else {
+        Err(Error::SelfTestFailed { check: name })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::selftest::check;
+    use crate::{self_test, Error};
+
+    #[test]
+    fn passes() {
+        self_test().unwrap();
+    }
+
+    #[test]
+    fn reports_failed_check() {
+        let e = check("wordlist digest", false).unwrap_err();
+        assert!(matches!(
+            e,
+            Error::SelfTestFailed {
+                check: "wordlist digest"
+            }
+        ));
+        assert_eq!(e.to_string(), "self-test failed: wordlist digest");
+    }
+}
@@ -0,0 +1,444 @@
+use crate::generate::check_num_words;
+use crate::{bytes_to_passphrase, passphrase_to_bytes, EntropySource, Error};
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+
+/// Prefix hashed before every share, so share commitments are not plain digests of the bytes.
+const COMMITMENT_LABEL: &[u8] = b"niceware shamir share";
+
+/// Split bytes into `shares` passphrases, any `threshold` of which recover the bytes.
+///
+/// This is plain Shamir secret sharing over GF(256), applied to every byte independently. Each
+/// share starts with a word encoding the threshold and the share's index, followed by as many
+/// words as `bytes` would encode to. Fewer than `threshold` shares reveal nothing about the bytes.
+///
+/// ## Errors
+///
+/// This function returns an InvalidSize error if the given slice has an odd number of bytes. It
+/// returns an InvalidThreshold error if `threshold` is zero or greater than `shares`, and an
+/// RNGError if the RNG failed to generate the random coefficients.
+///
+/// ## Examples
+///
+/// ```
+/// let secret = [0, 0, 17, 212, 12, 140, 90, 246];
+/// let shares = niceware::shamir_split(&secret, 2, 3, &mut rand::thread_rng()).unwrap();
+/// assert_eq!(niceware::shamir_combine(&shares[1..]).unwrap(), secret);
+/// ```
+pub fn shamir_split<R: EntropySource>(
+    bytes: &[u8],
+    threshold: u8,
+    shares: u8,
+    rng: &mut R,
+) -> Result<Vec<Vec<&'static str>>, Error> {
+    if bytes.len() % 2 != 0 {
+        return Err(Error::InvalidSize { size: bytes.len() });
+    }
+    if threshold == 0 || threshold > shares {
+        return Err(Error::InvalidThreshold { threshold, shares });
+    }
+
+    // Coefficients of degree 1 and up for every byte; degree 0 is the byte itself.
+    let degree = usize::from(threshold) - 1;
+    let mut coefficients = vec![0; bytes.len() * degree];
+    rng.fill_entropy(&mut coefficients)?;
+
+    (1..=shares)
+        .map(|x| {
+            let mut share = Vec::with_capacity(bytes.len() + 2);
+            share.extend([threshold, x]);
+            share.extend(bytes.iter().enumerate().map(|(i, &secret)| {
+                let higher = &coefficients[i * degree..(i + 1) * degree];
+                higher
+                    .iter()
+                    .rev()
+                    .chain(Some(&secret))
+                    .fold(0, |acc, &coefficient| gf_mul(acc, x) ^ coefficient)
+            }));
+            bytes_to_passphrase(&share)
+        })
+        .collect()
+}
+
+/// Generate a random secret of `num_words` words directly as `shares` shares.
+///
+/// Instead of drawing a secret and splitting it, the first `threshold` shares are drawn at random
+/// and the others are interpolated from them, so the secret itself is never computed or stored.
+/// The shares are indistinguishable from those of `shamir_split`, and the secret is uniformly
+/// random.
+///
+/// This is not split knowledge: all shares pass through this process, and any `threshold` of them
+/// rebuild the secret, so whoever can read its memory while it runs can recover the key. Run it
+/// on a machine every custodian trusts, hand out the shares, and use `shamir_commit` so each
+/// custodian can later check the share they were given.
+///
+/// ## Errors
+///
+/// This function returns a TooManyWords error if `num_words` is greater than 512. It returns an
+/// InvalidThreshold error if `threshold` is zero or greater than `shares`, and an RNGError if the
+/// RNG failed to generate bytes.
+///
+/// ## Examples
+///
+/// ```
+/// let shares = niceware::shamir_generate(8, 2, 3, &mut rand::thread_rng()).unwrap();
+/// let secret = niceware::shamir_combine(&shares[..2]).unwrap();
+/// assert_eq!(secret.len(), 16);
+/// assert_eq!(niceware::shamir_combine(&shares[1..]).unwrap(), secret);
+/// ```
+pub fn shamir_generate<R: EntropySource>(
+    num_words: usize,
+    threshold: u8,
+    shares: u8,
+    rng: &mut R,
+) -> Result<Vec<Vec<&'static str>>, Error> {
+    check_num_words(num_words)?;
+    if threshold == 0 || threshold > shares {
+        return Err(Error::InvalidThreshold { threshold, shares });
+    }
+
+    let len = num_words * 2;
+    let mut drawn = vec![0; len * usize::from(threshold)];
+    rng.fill_entropy(&mut drawn)?;
+    let xs: Vec<u8> = (1..=threshold).collect();
+
+    (1..=shares)
+        .map(|x| {
+            let mut share = Vec::with_capacity(len + 2);
+            share.extend([threshold, x]);
+            if x <= threshold {
+                let i = usize::from(x - 1);
+                share.extend(&drawn[i * len..(i + 1) * len]);
+            } else {
+                let weights = lagrange_weights(&xs, x);
+                share.extend((0..len).map(|byte| {
+                    drawn
+                        .chunks_exact(len)
+                        .zip(&weights)
+                        .fold(0, |acc, (ys, &weight)| acc ^ gf_mul(ys[byte], weight))
+                }));
+            }
+            bytes_to_passphrase(&share)
+        })
+        .collect()
+}
+
+/// Recover bytes from passphrases created by `shamir_split`.
+///
+/// Any `threshold` distinct shares are enough; extra shares are ignored once enough were found.
+/// Giving the same share twice is harmless.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary. It returns
+/// a NotEnoughShares error if fewer distinct shares than the threshold are given, and an
+/// InconsistentShares error if the shares disagree on threshold or length, or two different
+/// shares claim the same index.
+pub fn shamir_combine<'a, S: AsRef<[&'a str]>>(shares: &[S]) -> Result<Vec<u8>, Error> {
+    let mut points: Vec<(u8, Vec<u8>)> = Vec::new();
+    let mut threshold = None;
+
+    for share in shares {
+        let bytes = passphrase_to_bytes(share.as_ref())?;
+        let (header, ys) = match bytes.as_slice() {
+            [k, x, ys @ ..] if *k != 0 && *x != 0 => ((*k, *x), ys),
+            _ => return Err(Error::InconsistentShares),
+        };
+        if *threshold.get_or_insert(header.0) != header.0 {
+            return Err(Error::InconsistentShares);
+        }
+        if points
+            .first()
+            .is_some_and(|(_, first)| first.len() != ys.len())
+        {
+            return Err(Error::InconsistentShares);
+        }
+        match points.iter().find(|(x, _)| *x == header.1) {
+            Some((_, existing)) if existing[..] != ys[..] => return Err(Error::InconsistentShares),
+            Some(_) => {}
+            None => points.push((header.1, ys.to_vec())),
+        }
+    }
+
+    let needed = threshold.unwrap_or(1);
+    if points.len() < usize::from(needed) {
+        return Err(Error::NotEnoughShares {
+            needed,
+            got: points.len(),
+        });
+    }
+    points.truncate(usize::from(needed));
+
+    let xs: Vec<u8> = points.iter().map(|&(x, _)| x).collect();
+    let weights = lagrange_weights(&xs, 0);
+
+    let len = points[0].1.len();
+    Ok((0..len)
+        .map(|i| {
+            points
+                .iter()
+                .zip(&weights)
+                .fold(0, |acc, ((_, ys), &weight)| acc ^ gf_mul(ys[i], weight))
+        })
+        .collect())
+}
+
+/// Commitments to a set of Shamir shares, to check shares against when they are handed back.
+///
+/// The commitment holds a SHA-256 digest of every share and nothing about the secret, so it can
+/// be given to all custodians or published. Digests of short shares can be brute-forced back into
+/// the share, though, so only publish commitments of shares of 8 words or more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShamirCommitment {
+    digests: Vec<[u8; 32]>,
+}
+
+impl ShamirCommitment {
+    /// Whether `share` is one of the committed shares.
+    ///
+    /// ## Errors
+    ///
+    /// This method returns an UnknownWord error if a word is not found in the dictionary.
+    pub fn verify(&self, share: &[&str]) -> Result<bool, Error> {
+        let digest = share_digest(&passphrase_to_bytes(share)?);
+        Ok(self.digests.contains(&digest))
+    }
+
+    /// The commitment as the concatenated 32-byte digests of the shares, for storage.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.digests.concat()
+    }
+
+    /// Read a commitment stored with `to_bytes`.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an InvalidSize error if the length of `bytes` is not a multiple of
+    /// 32.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() % 32 != 0 {
+            return Err(Error::InvalidSize { size: bytes.len() });
+        }
+        let digests = bytes
+            .chunks_exact(32)
+            .map(|chunk| chunk.try_into().expect("chunk of 32 bytes"))
+            .collect();
+        Ok(ShamirCommitment { digests })
+    }
+}
+
+/// Commit to the shares created by `shamir_generate` or `shamir_split`.
+///
+/// The secret is not needed and not computed. Give the commitment to every custodian, and check
+/// returned shares with `ShamirCommitment::verify` before combining them, so a mistyped or
+/// swapped share is caught before it yields a wrong key.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// let shares = niceware::shamir_generate(8, 2, 3, &mut rand::thread_rng()).unwrap();
+/// let commitment = niceware::shamir_commit(&shares).unwrap();
+/// assert!(commitment.verify(&shares[2]).unwrap());
+/// assert!(!commitment.verify(&shares[2][..8]).unwrap());
+/// ```
+pub fn shamir_commit<'a, S: AsRef<[&'a str]>>(shares: &[S]) -> Result<ShamirCommitment, Error> {
+    let digests = shares
+        .iter()
+        .map(|share| Ok(share_digest(&passphrase_to_bytes(share.as_ref())?)))
+        .collect::<Result<_, Error>>()?;
+    Ok(ShamirCommitment { digests })
+}
+
+fn share_digest(share: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(COMMITMENT_LABEL);
+    hasher.update(share);
+    hasher.finalize().into()
+}
+
+/// Weights of the values at `xs` in the Lagrange interpolation of the polynomial at `at`.
+fn lagrange_weights(xs: &[u8], at: u8) -> Vec<u8> {
+    xs.iter()
+        .map(|&xi| {
+            xs.iter()
+                .filter(|&&xj| xj != xi)
+                .fold(1, |acc, &xj| gf_mul(acc, gf_div(at ^ xj, xi ^ xj)))
+        })
+        .collect()
+}
+
+/// Multiplication in GF(256) with the AES reduction polynomial.
+pub(crate) fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Division in GF(256); `b` must not be zero.
+pub(crate) fn gf_div(a: u8, b: u8) -> u8 {
+    // b^254 is the multiplicative inverse of b.
+    let mut inverse = 1;
+    let mut power = b;
+    let mut exponent = 254u8;
+    while exponent != 0 {
+        if exponent & 1 != 0 {
+            inverse = gf_mul(inverse, power);
+        }
+        power = gf_mul(power, power);
+        exponent >>= 1;
+    }
+    gf_mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shamir::{gf_div, gf_mul};
+    use crate::{
+        shamir_combine, shamir_commit, shamir_generate, shamir_split, Error, ShamirCommitment,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const SECRET: [u8; 16] = [
+        0, 0, 17, 212, 12, 140, 90, 246, 46, 83, 254, 60, 54, 169, 255, 255,
+    ];
+
+    #[test]
+    fn field_arithmetic() {
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        for a in 1..=255 {
+            assert_eq!(gf_mul(gf_div(1, a), a), 1);
+        }
+    }
+
+    #[test]
+    fn any_threshold_subset_recovers() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let shares = shamir_split(&SECRET, 3, 5, &mut rng).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert!(shares.iter().all(|share| share.len() == 9));
+
+        for a in 0..5 {
+            for b in a + 1..5 {
+                for c in b + 1..5 {
+                    let subset = [&shares[a], &shares[c], &shares[b]];
+                    assert_eq!(shamir_combine(&subset).unwrap(), SECRET);
+                }
+            }
+        }
+        assert_eq!(shamir_combine(&shares).unwrap(), SECRET);
+    }
+
+    #[test]
+    fn threshold_one_and_empty_secret() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let shares = shamir_split(&SECRET, 1, 2, &mut rng).unwrap();
+        assert_eq!(&shares[0][1..], &shares[1][1..]);
+        assert_eq!(shamir_combine(&shares[1..]).unwrap(), SECRET);
+
+        let shares = shamir_split(&[], 2, 2, &mut rng).unwrap();
+        assert_eq!(shamir_combine(&shares).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn invalid_input() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(
+            shamir_split(&SECRET, 4, 3, &mut rng)
+                .unwrap_err()
+                .to_string(),
+            "threshold 4 must be between 1 and number of shares 3"
+        );
+        assert!(matches!(
+            shamir_split(&SECRET, 0, 3, &mut rng),
+            Err(Error::InvalidThreshold { .. })
+        ));
+
+        let shares = shamir_split(&SECRET, 2, 3, &mut rng).unwrap();
+        assert_eq!(
+            shamir_combine(&[&shares[0], &shares[0]])
+                .unwrap_err()
+                .to_string(),
+            "2 shares needed but only 1 given"
+        );
+
+        let other = shamir_split(&SECRET, 3, 3, &mut rng).unwrap();
+        assert!(matches!(
+            shamir_combine(&[&shares[0], &other[1]]),
+            Err(Error::InconsistentShares)
+        ));
+        assert!(matches!(
+            shamir_combine(&[&shares[0][..], &shares[1][..5]]),
+            Err(Error::InconsistentShares)
+        ));
+    }
+
+    #[test]
+    fn generated_shares() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let shares = shamir_generate(4, 3, 5, &mut rng).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert!(shares.iter().all(|share| share.len() == 5));
+
+        let secret = shamir_combine(&shares[..3]).unwrap();
+        assert_eq!(secret.len(), 8);
+        for a in 0..5 {
+            for b in a + 1..5 {
+                for c in b + 1..5 {
+                    let subset = [&shares[a], &shares[b], &shares[c]];
+                    assert_eq!(shamir_combine(&subset).unwrap(), secret);
+                }
+            }
+        }
+
+        let shares = shamir_generate(2, 1, 2, &mut rng).unwrap();
+        assert_eq!(shares[0][1..], shares[1][1..]);
+        assert!(matches!(
+            shamir_generate(2, 3, 2, &mut rng),
+            Err(Error::InvalidThreshold { .. })
+        ));
+        assert!(matches!(
+            shamir_generate(513, 2, 2, &mut rng),
+            Err(Error::TooManyWords { .. })
+        ));
+    }
+
+    #[test]
+    fn commitment() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let shares = shamir_generate(8, 2, 3, &mut rng).unwrap();
+        let commitment = shamir_commit(&shares).unwrap();
+        assert!(shares.iter().all(|share| commitment.verify(share).unwrap()));
+
+        let other = shamir_generate(8, 2, 3, &mut rng).unwrap();
+        assert!(!commitment.verify(&other[0]).unwrap());
+        let mut swapped = shares[1].clone();
+        swapped.swap(3, 4);
+        assert!(!commitment.verify(&swapped).unwrap());
+        assert!(matches!(
+            commitment.verify(&["notaword"]),
+            Err(Error::UnknownWord { .. })
+        ));
+
+        let bytes = commitment.to_bytes();
+        assert_eq!(bytes.len(), 96);
+        assert_eq!(ShamirCommitment::from_bytes(&bytes).unwrap(), commitment);
+        assert!(matches!(
+            ShamirCommitment::from_bytes(&bytes[1..]),
+            Err(Error::InvalidSize { size: 95 })
+        ));
+    }
+}
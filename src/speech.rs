@@ -0,0 +1,151 @@
+use crate::{passphrase_to_bytes, word_to_index, Error};
+
+/// Hesitations that speech recognizers transcribe and that are not in the dictionary.
+const FILLERS: &[&str] = &["er", "erm", "hm", "hmm", "mm", "uhm", "um", "umm"];
+
+/// Numbers recognizers write as digits, all of which are dictionary words when spelled out.
+const NUMBERS: [&str; 21] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+];
+
+/// Rewrite speech-to-text output of a dictated passphrase into lowercase words separated by
+/// spaces.
+///
+/// Voice assistants and dictation software transcribe a spoken phrase with capitals, punctuation
+/// and their own idea of words. This lowercases the text and drops punctuation and apostrophes,
+/// then:
+///
+/// - drops hesitations such as "um" and "erm", and the scaffolding "the word" before a word;
+/// - writes numbers from 0 to 20 recognized as digits out in words, so "2" becomes "two";
+/// - joins hyphenated recognitions such as "bio-engineering" if the joined word is in the
+///   dictionary, and otherwise splits them, so slugs like "a-zyzzyva" still work;
+/// - joins runs of spelled-out letters such as "z y z z y v a" if they spell a dictionary word.
+///
+/// Anything else is kept, so an unrecognized word is still reported when decoding.
+///
+/// ## Examples
+///
+/// ```
+/// let heard = "Um, the word Bio-Engineering. Then 2… Z Y Z Z Y V A!";
+/// assert_eq!(
+///     niceware::normalize_speech(heard),
+///     "bioengineering then two zyzzyva"
+/// );
+/// ```
+pub fn normalize_speech(text: &str) -> String {
+    let lower = text.to_lowercase().replace(['\'', '\u{2019}'], "");
+    let mut tokens: Vec<&str> = Vec::new();
+    for token in lower.split(|c: char| !(c.is_alphanumeric() || c == '-')) {
+        let pieces: Vec<&str> = token.split('-').filter(|piece| !piece.is_empty()).collect();
+        if pieces.len() > 1 && is_word(&pieces.concat()) {
+            tokens.push(token);
+        } else {
+            tokens.extend(pieces);
+        }
+    }
+
+    let mut words: Vec<String> = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        if FILLERS.contains(&token) {
+            i += 1;
+        } else if token == "the" && tokens.get(i + 1) == Some(&"word") && i + 2 < tokens.len() {
+            i += 2;
+        } else if let Some(number) = token.parse().ok().and_then(|n: usize| NUMBERS.get(n)) {
+            words.push(number.to_string());
+            i += 1;
+        } else {
+            let run = tokens[i..]
+                .iter()
+                .take_while(|token| token.chars().count() == 1)
+                .count();
+            let spelled = tokens[i..i + run].concat();
+            if run > 1 && is_word(&spelled) {
+                words.push(spelled);
+                i += run;
+            } else {
+                words.push(token.replace('-', ""));
+                i += 1;
+            }
+        }
+    }
+    words.join(" ")
+}
+
+/// Decode speech-to-text output of a dictated passphrase into bytes.
+///
+/// This is `passphrase_to_bytes` on the words of `normalize_speech(text)`.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if a word is not found in the dictionary.
+///
+/// ## Examples
+///
+/// ```
+/// let bytes = niceware::speech_to_bytes("A. Bioengineering, um, zyzzyva.").unwrap();
+/// assert_eq!(bytes, [0, 0, 17, 212, 255, 255]);
+/// ```
+pub fn speech_to_bytes(text: &str) -> Result<Vec<u8>, Error> {
+    let normalized = normalize_speech(text);
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    passphrase_to_bytes(&words)
+}
+
+fn is_word(word: &str) -> bool {
+    word_to_index(word).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{normalize_speech, speech_to_bytes, Error};
+
+    #[test]
+    fn normalizes_recognitions() {
+        assert_eq!(normalize_speech(""), "");
+        assert_eq!(normalize_speech("Um... uh?"), "uh");
+        assert_eq!(normalize_speech("a-zyzzyva"), "a zyzzyva");
+        assert_eq!(normalize_speech("a a"), "a a");
+        assert_eq!(normalize_speech("Z-Y-Z-Z-Y-V-A"), "zyzzyva");
+        assert_eq!(normalize_speech("10 21 007"), "ten 21 seven");
+        assert_eq!(normalize_speech("gobbled the word"), "gobbled the word");
+        assert_eq!(
+            normalize_speech("The word gobbled, the word creneled."),
+            "gobbled creneled"
+        );
+        assert_eq!(normalize_speech("Gobbled’s"), "gobbleds");
+    }
+
+    #[test]
+    fn decodes_speech() {
+        assert_eq!(
+            speech_to_bytes("Erm, the word A; the word bio-engineering; z y z z y v a.").unwrap(),
+            [0, 0, 17, 212, 255, 255]
+        );
+        assert!(matches!(
+            speech_to_bytes("bulbasaur"),
+            Err(Error::UnknownWord { .. })
+        ));
+    }
+}
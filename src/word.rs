@@ -0,0 +1,111 @@
+use crate::{index_to_word, word_to_index, Error, Passphrase};
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+/// A word of the dictionary, checked when it is created.
+///
+/// It stores the word's index, so it is as small as a `u16`, and lets APIs take words that are
+/// known to be valid: an invalid word is caught when it is parsed, not when the passphrase is
+/// decoded later. A collection of words becomes a `Passphrase` without any further checks.
+///
+/// ## Examples
+///
+/// ```
+/// use niceware::{Passphrase, Word};
+///
+/// let word: Word = "Zyzzyva".parse().unwrap();
+/// assert_eq!((word.index(), word.as_str()), (65535, "zyzzyva"));
+/// assert!("bulbasaur".parse::<Word>().is_err());
+///
+/// let words = vec![Word::from_index(0), word];
+/// let phrase: Passphrase = words.into_iter().collect();
+/// assert_eq!(phrase.as_bytes(), &[0, 0, 255, 255]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Word(u16);
+
+impl Word {
+    /// The word at `index` in the dictionary.
+    pub fn from_index(index: u16) -> Self {
+        Word(index)
+    }
+
+    /// Index of the word in the dictionary, the value of the two bytes it encodes.
+    pub fn index(self) -> u16 {
+        self.0
+    }
+
+    /// The word, in lowercase.
+    pub fn as_str(self) -> &'static str {
+        index_to_word(self.0)
+    }
+}
+
+impl FromStr for Word {
+    type Err = Error;
+
+    /// Look a word up in the dictionary, ignoring ASCII case.
+    fn from_str(word: &str) -> Result<Self, Error> {
+        word_to_index(word).map(Word)
+    }
+}
+
+impl AsRef<str> for Word {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<Word> for u16 {
+    fn from(word: Word) -> u16 {
+        word.0
+    }
+}
+
+impl FromIterator<Word> for Passphrase {
+    fn from_iter<I: IntoIterator<Item = Word>>(words: I) -> Self {
+        let bytes = words
+            .into_iter()
+            .flat_map(|word| word.0.to_be_bytes())
+            .collect();
+        Passphrase::from_bytes(bytes).expect("two bytes per word")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Passphrase, Word};
+
+    #[test]
+    fn parse_and_collect() {
+        let word: Word = "BIOENGINEERING".parse().unwrap();
+        assert_eq!(word.index(), 0x11d4);
+        assert_eq!(word.to_string(), "bioengineering");
+        assert_eq!(u16::from(word), 0x11d4);
+        assert_eq!(Word::from_index(0x11d4), word);
+        assert!(matches!(
+            "ninetales".parse::<Word>(),
+            Err(Error::UnknownWord { .. })
+        ));
+        assert!(matches!(
+            "zyzzyvа".parse::<Word>(),
+            Err(Error::NonAsciiInput { .. })
+        ));
+
+        let words = [Word::from_index(0), word, Word::from_index(0xffff)];
+        let phrase: Passphrase = words.iter().copied().collect();
+        assert_eq!(phrase.to_string(), "a bioengineering zyzzyva");
+        assert_eq!(Passphrase::from_words(words).unwrap(), phrase);
+        assert_eq!(
+            crate::passphrase_to_bytes(words).unwrap(),
+            phrase.as_bytes()
+        );
+    }
+}
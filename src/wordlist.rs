@@ -0,0 +1,419 @@
+use crate::words::ALL_WORDS;
+use crate::{english_word_to_index, Error};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::OnceLock;
+
+static DEFAULT_WORDLIST: OnceLock<&'static dyn WordList> = OnceLock::new();
+
+/// A list of exactly 2^16 words that bytes can be encoded with.
+///
+/// The crate's own list is `English`. Other languages can implement this trait and be passed to
+/// `passphrase_to_bytes_detect` alongside it.
+pub trait WordList: Sync {
+    /// Name of the list, typically its language.
+    fn name(&self) -> &str;
+
+    /// Word for the given index.
+    fn word(&self, index: u16) -> &'static str;
+
+    /// Index of the given word, or `None` if it is not in the list.
+    ///
+    /// Implementations decide how lenient matching is; `English` ignores ASCII case.
+    fn index_of(&self, word: &str) -> Option<u16>;
+}
+
+impl fmt::Debug for dyn WordList + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("WordList").field(&self.name()).finish()
+    }
+}
+
+/// The English wordlist used by the rest of this crate.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+impl WordList for English {
+    fn name(&self) -> &str {
+        "english"
+    }
+
+    fn word(&self, index: u16) -> &'static str {
+        ALL_WORDS[usize::from(index)]
+    }
+
+    fn index_of(&self, word: &str) -> Option<u16> {
+        english_word_to_index(word).ok()
+    }
+}
+
+/// Make `list` the wordlist of the convenience functions, for the rest of the process.
+///
+/// Applications that load or compile their own list can register it once at startup instead of
+/// threading it through every call site. From then on, functions that turn bytes into words and
+/// back, such as `bytes_to_passphrase`, `passphrase_to_bytes`, `Word` and `Passphrase`, use it,
+/// and every generator draws its words from it; `self_test`, `verify_compatibility_with_upstream`
+/// and the wordlist label of backup sheets check it. Functions built on the properties of the
+/// English list, or on compatibility with the original implementation, keep using `English`:
+/// prefix completion with `PartialWord`, metal backup plates, `RadixCodec`, the `compat` module,
+/// `words` and `wordlist_digest`.
+///
+/// ## Errors
+///
+/// This function returns a DefaultWordlistAlreadySet error if a default wordlist was registered
+/// before, even the same one.
+///
+/// ## Examples
+///
+/// ```
+/// use niceware::{English, WordList};
+///
+/// /// The English list in reverse order.
+/// struct Reversed;
+///
+/// impl WordList for Reversed {
+///     fn name(&self) -> &str {
+///         "reversed"
+///     }
+///
+///     fn word(&self, index: u16) -> &'static str {
+///         English.word(!index)
+///     }
+///
+///     fn index_of(&self, word: &str) -> Option<u16> {
+///         English.index_of(word).map(|index| !index)
+///     }
+/// }
+///
+/// niceware::set_default_wordlist(&Reversed).unwrap();
+/// assert_eq!(niceware::default_wordlist().name(), "reversed");
+/// assert_eq!(niceware::bytes_to_passphrase(&[0, 0]).unwrap(), ["zyzzyva"]);
+/// assert_eq!(niceware::passphrase_to_bytes(&["a"]).unwrap(), [255, 255]);
+/// assert!(niceware::set_default_wordlist(&English).is_err());
+/// ```
+pub fn set_default_wordlist(list: &'static dyn WordList) -> Result<(), Error> {
+    DEFAULT_WORDLIST
+        .set(list)
+        .map_err(|_| Error::DefaultWordlistAlreadySet)
+}
+
+/// The wordlist of the convenience functions: the one registered with `set_default_wordlist`, or
+/// `English`.
+pub fn default_wordlist() -> &'static dyn WordList {
+    custom_default_wordlist().unwrap_or(&English)
+}
+
+/// The wordlist registered with `set_default_wordlist`, if any.
+pub(crate) fn custom_default_wordlist() -> Option<&'static dyn WordList> {
+    DEFAULT_WORDLIST.get().copied()
+}
+
+/// Words of the default wordlist in index order, for generators that filter the list.
+pub(crate) fn default_words() -> impl Iterator<Item = &'static str> + Clone {
+    (0..=u16::MAX).map(crate::index_to_word)
+}
+
+/// Words of the default wordlist ordered by length in bytes, shortest first.
+///
+/// The order is computed once per list: once for `English` and once for a list registered with
+/// `set_default_wordlist`, which cannot change after that.
+pub(crate) fn default_words_by_len() -> &'static [&'static str] {
+    static ENGLISH: OnceLock<Vec<&'static str>> = OnceLock::new();
+    static CUSTOM: OnceLock<Vec<&'static str>> = OnceLock::new();
+    let by_len = |word: fn(u16) -> &'static str| {
+        let mut words: Vec<&'static str> = (0..=u16::MAX).map(word).collect();
+        words.sort_by_key(|word| word.len());
+        words
+    };
+    match custom_default_wordlist() {
+        Some(_) => CUSTOM.get_or_init(|| by_len(|index| default_wordlist().word(index))),
+        None => ENGLISH.get_or_init(|| by_len(|index| ALL_WORDS[usize::from(index)])),
+    }
+}
+
+/// Words of the default wordlist at most `len` bytes long, shortest first.
+pub(crate) fn default_words_up_to_len(len: usize) -> &'static [&'static str] {
+    let words = default_words_by_len();
+    &words[..words.partition_point(|word| word.len() <= len)]
+}
+
+/// Decode words with whichever of the given lists contains all of them.
+///
+/// Lists are tried in order, and the bytes are returned together with the first list that knows
+/// every word. Put the most likely list first if a phrase could be valid in more than one.
+///
+/// ## Errors
+///
+/// This function returns an UnknownWord error if no list contains every word. The word reported
+/// is the first unknown word in the list that recognized the most words. It returns a NoWordlists
+/// error if `lists` is empty.
+///
+/// ## Examples
+///
+/// ```
+/// use niceware::{English, WordList};
+///
+/// let (bytes, list) = niceware::passphrase_to_bytes_detect(&["zyzzyva"], &[&English]).unwrap();
+/// assert_eq!(bytes, &[255, 255]);
+/// assert_eq!(list.name(), "english");
+/// ```
+pub fn passphrase_to_bytes_detect<'l>(
+    words: &[&str],
+    lists: &[&'l dyn WordList],
+) -> Result<(Vec<u8>, &'l dyn WordList), Error> {
+    let mut best: Option<(usize, &str)> = None;
+
+    for &list in lists {
+        let mut bytes = Vec::with_capacity(words.len() * 2);
+        let mut unknown = None;
+        let mut known = 0;
+        for word in words {
+            match list.index_of(word) {
+                Some(index) => {
+                    bytes.extend(&index.to_be_bytes());
+                    known += 1;
+                }
+                None => {
+                    unknown.get_or_insert(*word);
+                }
+            }
+        }
+        match unknown {
+            None => return Ok((bytes, list)),
+            Some(word) if best.map_or(true, |(most, _)| known > most) => best = Some((known, word)),
+            Some(_) => {}
+        }
+    }
+
+    match best {
+        Some((_, word)) => Err(Error::UnknownWord {
+            word: word.to_string(),
+        }),
+        None => Err(Error::NoWordlists),
+    }
+}
+
+/// Number of words in the wordlist, 2^16.
+pub const WORD_COUNT: usize = 1 << 16;
+
+/// Iterate over the wordlist in index order.
+///
+/// The word at position `i` encodes the two bytes of `i` as a big-endian `u16`. Words are unique,
+/// lowercase ASCII letters only, and sorted in ascending byte order, so a prefix matches a
+/// contiguous range of the list; autocomplete can binary search the collected list.
+///
+/// ## Examples
+///
+/// ```
+/// let mut words = niceware::words();
+/// assert_eq!(words.len(), niceware::WORD_COUNT);
+/// assert_eq!(words.next(), Some("a"));
+/// assert_eq!(words.last(), Some("zyzzyva"));
+/// ```
+pub fn words() -> impl ExactSizeIterator<Item = &'static str> + DoubleEndedIterator + Clone {
+    ALL_WORDS.iter().copied()
+}
+
+/// SHA-256 digest of the upstream niceware wordlist this crate tracks.
+///
+/// The digest is taken over every word followed by a newline, in wordlist order. In JavaScript the
+/// same value is `sha256(wordlist.join('\n') + '\n')`.
+pub const UPSTREAM_WORDLIST_DIGEST: [u8; 32] = [
+    0xe0, 0xab, 0x85, 0xe0, 0xec, 0x3f, 0x30, 0x1a, 0x7b, 0xab, 0xa1, 0xce, 0xd9, 0xb0, 0xa4, 0xc1,
+    0x67, 0xa3, 0xa1, 0xa6, 0x94, 0x84, 0xa6, 0xbe, 0xa5, 0x4d, 0x11, 0x53, 0xc6, 0x04, 0x35, 0x9a,
+];
+
+/// Compute the digest of the wordlist compiled into this crate.
+///
+/// See `UPSTREAM_WORDLIST_DIGEST` for how the digest is taken.
+pub fn wordlist_digest() -> [u8; 32] {
+    digest_words(words())
+}
+
+/// Digest of the default wordlist, taken like `wordlist_digest`.
+pub(crate) fn default_wordlist_digest() -> [u8; 32] {
+    digest_words(default_words())
+}
+
+fn digest_words<'a>(words: impl Iterator<Item = &'a str>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for word in words {
+        hasher.update(word.as_bytes());
+        hasher.update(b"\n");
+    }
+    hasher.finalize().into()
+}
+
+/// Check that another implementation uses the same wordlist as this crate.
+///
+/// `digest` is the wordlist digest computed by the other side, for example the JavaScript
+/// implementation. When it matches, both sides turn the same bytes into the same words and back.
+/// It is compared with the digest of the wordlist this crate encodes with: the one registered with
+/// `set_default_wordlist`, or the compiled-in list.
+///
+/// ## Errors
+///
+/// This function returns an IncompatibleWordlist error if the digest does not match that
+/// wordlist.
+pub fn verify_compatibility_with_upstream(digest: &[u8; 32]) -> Result<(), Error> {
+    if default_wordlist_digest() == *digest {
+        Ok(())
+    } else {
+        Err(Error::IncompatibleWordlist)
+    }
+}
+
+/// Magic bytes at the start of the output of `wordlist_index`.
+pub const WORDLIST_INDEX_MAGIC: [u8; 8] = *b"NICEWIDX";
+
+/// Serialize the word-to-index mapping as a sorted binary blob for tools outside Rust.
+///
+/// The format is stable; a change would bump the version. All integers are big-endian.
+///
+/// | Bytes | Content |
+/// |---|---|
+/// | 8 | `WORDLIST_INDEX_MAGIC`, `NICEWIDX` |
+/// | 2 | Format version, 1 |
+/// | 4 | Number of words `n`, 65536 |
+/// | 4 × (`n` + 1) | Offset of every word in the data, then the length of the data |
+/// | rest | Data: the words in index order, ASCII, without separators |
+///
+/// Word `i` is `data[offset[i]..offset[i + 1]]` and encodes the bytes of `i` as a big-endian u16.
+/// Words are sorted, so a reader can binary search the offsets to map a word to its index without
+/// loading the list into another structure.
+///
+/// ## Examples
+///
+/// ```
+/// let index = niceware::wordlist_index();
+/// assert_eq!(&index[..8], b"NICEWIDX");
+/// ```
+pub fn wordlist_index() -> Vec<u8> {
+    let data_len: usize = ALL_WORDS.iter().map(|word| word.len()).sum();
+    let mut index = Vec::with_capacity(14 + 4 * (ALL_WORDS.len() + 1) + data_len);
+    index.extend(&WORDLIST_INDEX_MAGIC);
+    index.extend(&1u16.to_be_bytes());
+    index.extend(&(ALL_WORDS.len() as u32).to_be_bytes());
+    let mut offset = 0u32;
+    for word in ALL_WORDS {
+        index.extend(&offset.to_be_bytes());
+        offset += word.len() as u32;
+    }
+    index.extend(&offset.to_be_bytes());
+    for word in ALL_WORDS {
+        index.extend(word.as_bytes());
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::words::ALL_WORDS;
+    use crate::{
+        bytes_to_passphrase, passphrase_to_bytes_detect, verify_compatibility_with_upstream,
+        wordlist_digest, wordlist_index, words, English, WordList, UPSTREAM_WORDLIST_DIGEST,
+        WORD_COUNT,
+    };
+    use std::sync::OnceLock;
+
+    /// A stand-in second language: the English list spelled backwards.
+    struct Reversed;
+
+    impl WordList for Reversed {
+        fn name(&self) -> &str {
+            "reversed"
+        }
+
+        fn word(&self, index: u16) -> &'static str {
+            static WORDS: OnceLock<Vec<String>> = OnceLock::new();
+            let words = WORDS.get_or_init(|| {
+                ALL_WORDS
+                    .iter()
+                    .map(|word| word.chars().rev().collect())
+                    .collect()
+            });
+            &words[usize::from(index)]
+        }
+
+        fn index_of(&self, word: &str) -> Option<u16> {
+            English.index_of(&word.chars().rev().collect::<String>())
+        }
+    }
+
+    #[test]
+    fn public_words() {
+        assert_eq!(words().len(), WORD_COUNT);
+        assert!(words().zip(words().skip(1)).all(|(a, b)| a < b));
+        assert!(words().all(|word| word.bytes().all(|b| b.is_ascii_lowercase())));
+        let index = 0x11d4;
+        assert_eq!(
+            words().nth(index),
+            Some(bytes_to_passphrase(&[0x11, 0xd4]).unwrap()[0])
+        );
+    }
+
+    #[test]
+    fn index_format() {
+        let index = wordlist_index();
+        assert_eq!(&index[..14], b"NICEWIDX\x00\x01\x00\x01\x00\x00");
+        let offsets: Vec<usize> = index[14..14 + 4 * (WORD_COUNT + 1)]
+            .chunks_exact(4)
+            .map(|offset| u32::from_be_bytes([offset[0], offset[1], offset[2], offset[3]]) as usize)
+            .collect();
+        let data = &index[14 + 4 * (WORD_COUNT + 1)..];
+        assert_eq!(*offsets.last().unwrap(), data.len());
+
+        let word = |i: usize| std::str::from_utf8(&data[offsets[i]..offsets[i + 1]]).unwrap();
+        assert_eq!(word(0), "a");
+        assert_eq!(word(0x11d4), bytes_to_passphrase(&[0x11, 0xd4]).unwrap()[0]);
+        assert_eq!(word(WORD_COUNT - 1), "zyzzyva");
+    }
+
+    #[test]
+    fn tracks_upstream() {
+        assert_eq!(wordlist_digest(), UPSTREAM_WORDLIST_DIGEST);
+        verify_compatibility_with_upstream(&UPSTREAM_WORDLIST_DIGEST).unwrap();
+    }
+
+    #[test]
+    fn detects_list() {
+        let lists: [&dyn WordList; 2] = [&English, &Reversed];
+
+        let (bytes, list) = passphrase_to_bytes_detect(&["a", "zyzzyva"], &lists).unwrap();
+        assert_eq!(bytes, &[0, 0, 255, 255]);
+        assert_eq!(list.name(), "english");
+
+        let (bytes, list) = passphrase_to_bytes_detect(&["avyzzyz", "hAA"], &lists).unwrap();
+        assert_eq!(bytes, &[255, 255, 0, 1]);
+        assert_eq!(list.name(), "reversed");
+        assert_eq!(list.word(1), "haa");
+        assert!((0..=u16::MAX).all(|index| list.index_of(list.word(index)) == Some(index)));
+    }
+
+    #[test]
+    fn detect_unknown_word() {
+        let lists: [&dyn WordList; 2] = [&English, &Reversed];
+        assert_eq!(
+            passphrase_to_bytes_detect(&["avyzzyz", "hAA", "zyzzyva"], &lists)
+                .unwrap_err()
+                .to_string(),
+            "unknown word: zyzzyva"
+        );
+        assert_eq!(
+            passphrase_to_bytes_detect(&["a"], &[])
+                .unwrap_err()
+                .to_string(),
+            "no wordlist to decode with"
+        );
+    }
+
+    #[test]
+    fn different_digest() {
+        assert_eq!(
+            verify_compatibility_with_upstream(&[0; 32])
+                .unwrap_err()
+                .to_string(),
+            "wordlist does not match upstream"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-/**
+/*
  * @fileoverview 2^16 English wordlist. Derived from
  * diracdeltas/niceware, which derivied it from
  * http://www-01.sil.org/linguistics/wordlists/english/.
@@ -0,0 +1,158 @@
+//! Generators with a custom default wordlist.
+//!
+//! The default wordlist can only be registered once per process, so these tests get their own
+//! binary.
+
+use niceware::{Capitalize, DicewareProfile, English, MemorableRecipe, WordList};
+use rand::{rngs::StdRng, SeedableRng};
+use std::sync::{Once, OnceLock};
+
+/// The English list with every word prefixed by a two-byte character.
+struct Accented;
+
+fn accented_words() -> &'static [String] {
+    static WORDS: OnceLock<Vec<String>> = OnceLock::new();
+    WORDS.get_or_init(|| niceware::words().map(|word| format!("é{}", word)).collect())
+}
+
+impl WordList for Accented {
+    fn name(&self) -> &str {
+        "accented"
+    }
+
+    fn word(&self, index: u16) -> &'static str {
+        &accented_words()[usize::from(index)]
+    }
+
+    fn index_of(&self, word: &str) -> Option<u16> {
+        let word = word.strip_prefix('é').or_else(|| word.strip_prefix('É'))?;
+        English.index_of(word)
+    }
+}
+
+fn register() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| niceware::set_default_wordlist(&Accented).unwrap());
+}
+
+fn assert_accented(words: &[&str]) {
+    assert!(
+        words.iter().all(|word| word.starts_with('é')),
+        "{:?}",
+        words
+    );
+}
+
+#[test]
+fn generators_draw_from_custom_list() {
+    register();
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let generated = niceware::generate_passphrase_excluding(8, &["éa"], &mut rng).unwrap();
+    assert_accented(&generated.words);
+    assert!(!generated.words.contains(&"éa"));
+    assert_eq!(generated.to_bytes().len(), 16);
+
+    let subset = ["éa", "ézyzzyva"];
+    let generated = niceware::generate_passphrase_from_subset(8, &subset, &mut rng).unwrap();
+    assert!(generated.words.iter().all(|word| subset.contains(word)));
+    assert_eq!(generated.entropy_bits, 8.0);
+    assert_eq!(generated.to_bytes().len(), 16);
+
+    let generated = niceware::generate_passphrase_max_len(64, " ", &mut rng).unwrap();
+    assert_accented(&generated.words);
+    assert!(generated.words.join(" ").len() <= 64);
+    generated.to_bytes();
+
+    let generated = niceware::generate_pronounceable_passphrase(4, 0, &mut rng).unwrap();
+    assert_accented(&generated.words);
+    generated.to_bytes();
+
+    let generated = niceware::generate_passphrase_without_confusables(
+        4,
+        niceware::DEFAULT_CONFUSABLE_RULES,
+        &mut rng,
+    )
+    .unwrap();
+    assert_accented(&generated.words);
+    generated.to_bytes();
+
+    let passphrase = DicewareProfile::default().generate(&mut rng).unwrap();
+    assert_accented(&passphrase.split(' ').collect::<Vec<_>>());
+}
+
+#[test]
+fn capitalizes_multibyte_first_letters() {
+    register();
+
+    let words =
+        niceware::bytes_to_capitalized_passphrase(&[0, 0, 255, 255], &[true, false]).unwrap();
+    assert_eq!(words, ["Éa", "ézyzzyva"]);
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    assert_eq!(
+        niceware::capitalized_passphrase_to_bytes(&words).unwrap(),
+        (vec![0, 0, 255, 255], vec![true, false])
+    );
+
+    let recipe = MemorableRecipe {
+        words: 4,
+        separator: "-",
+        capitalize: Capitalize::All,
+        include_number: false,
+    };
+    let password = recipe.generate(&mut StdRng::seed_from_u64(2)).unwrap();
+    assert!(password.split('-').all(|word| word.starts_with('É')));
+}
+
+#[test]
+fn english_apis_ignore_custom_list() {
+    register();
+
+    assert_eq!(
+        niceware::compat::bytes_to_passphrase(&[0, 0, 255, 255]).unwrap(),
+        ["a", "zyzzyva"]
+    );
+    assert_eq!(
+        niceware::compat::passphrase_to_bytes(&["a", "zyzzyva"]).unwrap(),
+        [0, 0, 255, 255]
+    );
+    #[cfg(any(feature = "os-rng", feature = "ring-rng"))]
+    {
+        let words = niceware::compat::generate_passphrase(8).unwrap();
+        assert!(niceware::compat::passphrase_to_bytes(&words).is_ok());
+    }
+
+    let rows = niceware::plate_layout(&["a", "bioengineering"]).unwrap();
+    assert_eq!(rows[1].letters, "bioeng");
+    assert_eq!(
+        niceware::plate_to_bytes(&["A", "BIOENG"]).unwrap(),
+        [0, 0, 17, 212]
+    );
+}
+
+#[test]
+fn checks_follow_custom_list() {
+    register();
+
+    niceware::self_test().unwrap();
+    assert_eq!(
+        niceware::wordlist_digest(),
+        niceware::UPSTREAM_WORDLIST_DIGEST
+    );
+    assert!(
+        niceware::verify_compatibility_with_upstream(&niceware::UPSTREAM_WORDLIST_DIGEST).is_err()
+    );
+}
+
+#[cfg(feature = "pdf")]
+#[test]
+fn backup_sheet_names_custom_list() {
+    register();
+
+    let options = niceware::BackupSheetOptions::default();
+    let phrase = niceware::Passphrase::from_words(["éa", "ézyzzyva"]).unwrap();
+    let pdf = niceware::render_backup_pdf(&phrase, &options).unwrap();
+    let pdf = String::from_utf8_lossy(&pdf);
+    assert!(pdf.contains("Wordlist: accented "));
+    assert!(!pdf.contains("Wordlist: accented e0ab85e0."));
+}